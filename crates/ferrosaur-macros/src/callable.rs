@@ -0,0 +1,162 @@
+use darling::{Error, Result};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{
+    parse::{Parse, Parser},
+    spanned::Spanned,
+    Attribute, ImplItemFn, ImplItemType, TraitItemFn, TraitItemType, Visibility,
+};
+use tap::{Pipe, Tap};
+
+use crate::{
+    util::{
+        expect_self_arg,
+        flag::FlagLike,
+        function::{CallFunction, FunctionIntent, FunctionSource},
+        interface::{DeriveInterface, InterfaceLike, OuterType, SomeFunc, SomeType},
+        no_default_fn, no_fn_body,
+        site::track_caller,
+        Caveat, RecoverableErrors,
+    },
+    Callable, Constructor, JsProp,
+};
+
+pub fn callable(_: Callable, item: TokenStream) -> Result<TokenStream> {
+    InterfaceLike::parse.parse2(item)?.derive(&DeriveCallback)
+}
+
+struct DeriveCallback;
+
+impl DeriveInterface for DeriveCallback {
+    fn impl_func(item: ImplItemFn) -> Result<SomeFunc> {
+        let ImplItemFn {
+            attrs,
+            vis,
+            defaultness,
+            sig,
+            block,
+        } = item;
+
+        let mut errors = Error::accumulator();
+
+        errors.handle(no_fn_body(Some(block)));
+        errors.handle(no_default_fn(defaultness));
+
+        errors.finish_with(SomeFunc { attrs, vis, sig })
+    }
+
+    fn trait_func(item: TraitItemFn) -> Result<SomeFunc> {
+        let TraitItemFn {
+            attrs,
+            default,
+            sig,
+            ..
+        } = item;
+
+        let mut errors = Error::accumulator();
+
+        errors.handle(no_fn_body(default));
+
+        let vis = Visibility::Inherited;
+
+        errors.finish_with(SomeFunc { attrs, vis, sig })
+    }
+
+    fn count_items(fns: usize, types: usize) -> Result<()> {
+        match (fns, types) {
+            (0, _) => Err(Error::custom("expected at least one fn")),
+            (_, _) => Ok(()),
+        }
+    }
+
+    fn derive_func(
+        SomeFunc {
+            attrs,
+            vis,
+            mut sig,
+        }: SomeFunc,
+        _: OuterType,
+    ) -> Result<TokenStream> {
+        let mut errors = Error::accumulator();
+
+        let (ctor, attrs) = expected_ctor_attr(attrs).and_recover(&mut errors);
+
+        let mut call = CallFunction::from_sig(&mut sig)
+            .and_recover(&mut errors)
+            .tap_mut(|call| call.source = FunctionSource::This);
+
+        if let Some(Constructor { class }) = ctor {
+            if class.is_some() {
+                "`class(...)` is not supported here\nthe value itself is used as the constructor"
+                    .pipe(Error::custom)
+                    .with_span(&sig.ident)
+                    .pipe(|e| errors.push(e));
+            }
+            if let FunctionIntent::Deferred = call.intent {
+                "constructor cannot return `JsPromise<...>`"
+                    .pipe(Error::custom)
+                    .with_span(&sig.output)
+                    .pipe(|e| errors.push(e));
+            }
+            call.intent = FunctionIntent::Constructed
+                .only(&sig)
+                .and_recover(&mut errors);
+        }
+
+        let fn_self = errors.handle(expect_self_arg(&sig.inputs, &sig.ident));
+
+        let rendered = call.render(fn_self, &sig.ident, &sig.generics);
+
+        let track_caller = track_caller(&sig);
+
+        errors.finish_with(quote! { #(#attrs)* #track_caller #vis #rendered })
+    }
+
+    fn unsupported<T, S: Spanned>(item: S) -> Result<T> {
+        Error::custom("only fn items are supported here")
+            .with_span(&item)
+            .pipe(Err)
+    }
+
+    fn impl_type(item: ImplItemType) -> Result<SomeType> {
+        Self::unsupported(item)
+    }
+
+    fn trait_type(item: TraitItemType) -> Result<SomeType> {
+        Self::unsupported(item)
+    }
+
+    fn derive_type(&self, item: SomeType, _: OuterType) -> Result<TokenStream> {
+        Self::unsupported(item.ident)
+    }
+}
+
+/// Take `#[js(new)]` out of `attrs`, which is the only `#[js(...)]` allowed here.
+fn expected_ctor_attr(attrs: Vec<Attribute>) -> Caveat<(Option<Constructor>, Vec<Attribute>)> {
+    let mut errors = Error::accumulator();
+
+    let mut ctor = None;
+    let mut rest = vec![];
+
+    // collected one at a time so that errors can point at the attribute
+    for attr in attrs {
+        let (props, attrs) =
+            FlagLike::<JsProp>::collect(vec![attr.clone()]).and_recover(&mut errors);
+
+        rest.extend(attrs);
+
+        props.into_iter().for_each(|FlagLike(prop)| match prop {
+            JsProp::New(FlagLike(new)) if ctor.is_none() => ctor = Some(new),
+            JsProp::New(_) => Error::custom("duplicate #[js(new)]")
+                .with_span(&attr)
+                .pipe(|e| errors.push(e)),
+            _ => Error::custom(
+                "only #[js(new)] is supported here\nfns without it call the value as a function",
+            )
+            .with_span(&attr)
+            .pipe(|e| errors.push(e)),
+        });
+    }
+
+    ((ctor, rest), errors.finish().err()).into()
+}
@@ -0,0 +1,412 @@
+use darling::{Error, Result};
+use heck::ToLowerCamelCase;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{
+    parse::{Parse, Parser},
+    FnArg, GenericArgument, ImplItem, ImplItemFn, ItemImpl, ItemStruct, Pat, PatIdent,
+    PathArguments, Receiver, ReturnType, Signature, Type, TypePath,
+};
+use tap::Pipe;
+
+use crate::{
+    util::{
+        flag::FlagLike, use_deno, use_prelude, v8::V8Conv, Caveat, FatalErrors, NewtypeMeta,
+        RecoverableErrors,
+    },
+    Class, ClassConstructor, Export, JsMember, Method,
+};
+
+pub fn class(Class { name }: Class, item: TokenStream) -> Result<TokenStream> {
+    let errors = Error::accumulator();
+
+    let (item, mut errors) = ItemStruct::parse.parse2(item).or_fatal(errors)?;
+
+    if !item.generics.params.is_empty() || item.generics.where_clause.is_some() {
+        Error::custom("must not have generics")
+            .with_span(&item.generics)
+            .pipe(|e| errors.push(e));
+    }
+
+    let ident = &item.ident;
+
+    let name = name
+        .into_inner()
+        .into_inner()
+        .unwrap_or_else(|| ident.to_string());
+
+    errors.finish()?;
+
+    Ok(quote! {
+        #item
+
+        const _: () = {
+            #use_prelude
+            #use_deno
+
+            #[automatically_derived]
+            impl ::ferrosaur::JsClass for #ident {
+                const NAME: &'static str = #name;
+            }
+
+            #[automatically_derived]
+            impl<'a> ToV8<'a> for #ident {
+                type Error = ::ferrosaur::Error;
+
+                fn to_v8(
+                    self,
+                    scope: &mut v8::HandleScope<'a>,
+                ) -> ::core::result::Result<v8::Local<'a, v8::Value>, Self::Error> {
+                    ::ferrosaur::new_instance(scope, self)
+                        .map(Into::into)
+                        .map_err(::ferrosaur::Error::other)
+                }
+            }
+        };
+    })
+}
+
+pub fn export(Export { name }: Export, item: TokenStream) -> Result<TokenStream> {
+    let errors = Error::accumulator();
+
+    let (mut item, mut errors) = ItemImpl::parse.parse2(item).or_fatal(errors)?;
+
+    if name.is_some() {
+        "`name` is only supported on fns in #[js(host_module)]\nuse #[js(class(name = ...))] instead"
+            .pipe(Error::custom)
+            .with_span(&item.self_ty)
+            .pipe(|e| errors.push(e));
+    }
+
+    if let Some((_, path, _)) = &item.trait_ {
+        Error::custom("expected an inherent impl, not a trait impl")
+            .with_span(path)
+            .pipe(|e| errors.push(e));
+    }
+
+    if !item.generics.params.is_empty() || item.generics.where_clause.is_some() {
+        Error::custom("must not have generics")
+            .with_span(&item.generics)
+            .pipe(|e| errors.push(e));
+    }
+
+    let self_ty = (*item.self_ty).clone();
+
+    let mut constructor = None;
+    let mut methods = vec![];
+
+    for item in item.items.iter_mut() {
+        let ImplItem::Fn(func) = item else {
+            continue;
+        };
+        let Some(member) = take_member(func).and_recover(&mut errors) else {
+            continue;
+        };
+        match member {
+            JsMember::Constructor(FlagLike(ctor)) => {
+                if constructor.is_some() {
+                    Error::custom("expected at most one #[js(constructor)]")
+                        .with_span(&func.sig.ident)
+                        .pipe(|e| errors.push(e));
+                }
+                if let Some(ctor) = errors.handle(export_constructor(ctor, func, &self_ty)) {
+                    constructor = Some(ctor);
+                }
+            }
+            JsMember::Method(FlagLike(method)) => {
+                if let Some(method) = errors.handle(export_method(method, func, &self_ty)) {
+                    methods.push(method);
+                }
+            }
+        }
+    }
+
+    let constructor = constructor.unwrap_or_else(|| {
+        quote! {
+            let _ = (scope, args);
+            Err(anyhow!(
+                "{} cannot be constructed from JavaScript",
+                <Self as ::ferrosaur::JsClass>::NAME
+            ))
+        }
+    });
+
+    errors.finish()?;
+
+    Ok(quote! {
+        #item
+
+        const _: () = {
+            #use_prelude
+            #use_deno
+
+            #[automatically_derived]
+            impl ::ferrosaur::ExportClass for #self_ty {
+                fn construct<'s>(
+                    scope: &mut v8::HandleScope<'s>,
+                    args: &v8::FunctionCallbackArguments<'s>,
+                ) -> Result<Self> {
+                    #constructor
+                }
+
+                fn define<'s>(
+                    scope: &mut v8::HandleScope<'s>,
+                    class: v8::Local<'s, v8::FunctionTemplate>,
+                ) -> Result<()> {
+                    let _ = (&scope, class);
+                    #(#methods)*
+                    Ok(())
+                }
+            }
+        };
+    })
+}
+
+/// Take `#[js(constructor)]` or `#[js(method)]` out of the attributes of `func`.
+fn take_member(func: &mut ImplItemFn) -> Caveat<Option<JsMember>> {
+    let mut errors = Error::accumulator();
+
+    let (items, rest) =
+        FlagLike::<JsMember>::collect(std::mem::take(&mut func.attrs)).and_recover(&mut errors);
+
+    func.attrs = rest;
+
+    if items.len() > 1 {
+        Error::custom("expected at most one of #[js(constructor)], #[js(method)]")
+            .with_span(&func.sig.ident)
+            .pipe(|e| errors.push(e));
+    }
+
+    let member = items.into_iter().next().map(|FlagLike(member)| member);
+
+    (member, errors.finish().err()).into()
+}
+
+fn export_constructor(
+    _: ClassConstructor,
+    func: &mut ImplItemFn,
+    self_ty: &Type,
+) -> Result<TokenStream> {
+    let mut errors = Error::accumulator();
+
+    if let Some(recv) = func.sig.receiver() {
+        Error::custom("constructor cannot take `self`")
+            .with_span(recv)
+            .pipe(|e| errors.push(e));
+    }
+
+    let ident = func.sig.ident.clone();
+    let context = format!("new {}", quote! { #self_ty });
+
+    let (casts, args) = errors.handle(from_inputs(&mut func.sig, &context)).unzip();
+
+    let unwrap = match &mut func.sig.output {
+        ReturnType::Type(_, ty) => result_ok_type(ty).map(|_| throw_err()),
+        ReturnType::Default => None,
+    };
+
+    errors.finish()?;
+
+    Ok(quote! {
+        #casts
+        let output = <#self_ty>::#ident(#args);
+        #unwrap
+        Ok(output)
+    })
+}
+
+fn export_method(
+    Method { name }: Method,
+    func: &mut ImplItemFn,
+    self_ty: &Type,
+) -> Result<TokenStream> {
+    let mut errors = Error::accumulator();
+
+    let ident = func.sig.ident.clone();
+
+    let name = name
+        .into_inner()
+        .into_inner()
+        .unwrap_or_else(|| ident.to_string().to_lower_camel_case());
+
+    let context = format!("{}.{name}", quote! { #self_ty });
+
+    let this = match func.sig.receiver() {
+        None => None,
+        Some(recv) => errors.handle(borrow_this(recv, self_ty)),
+    };
+
+    let kind = match this {
+        None => quote! { ::ferrosaur::MethodKind::Static },
+        Some(_) => quote! { ::ferrosaur::MethodKind::Instance },
+    };
+
+    let (casts, args) = errors.handle(from_inputs(&mut func.sig, &context)).unzip();
+
+    let output = errors.handle(from_output(&mut func.sig, &context));
+
+    errors.finish()?;
+
+    let call = match this {
+        None => quote! { <#self_ty>::#ident(#args) },
+        Some(this) => quote! {{
+            #this
+            <#self_ty>::#ident(this, #args)
+        }},
+    };
+
+    Ok(quote! {{
+        struct Method;
+
+        impl ::ferrosaur::Method for Method {
+            fn call<'s>(
+                scope: &mut v8::HandleScope<'s>,
+                args: &v8::FunctionCallbackArguments<'s>,
+            ) -> Result<v8::Local<'s, v8::Value>> {
+                #casts
+                let output = #call;
+                #output
+            }
+        }
+
+        ::ferrosaur::define_method::<Method>(scope, class, #name, #kind)?;
+    }})
+}
+
+fn borrow_this(recv: &Receiver, self_ty: &Type) -> Result<TokenStream> {
+    let unwrap = quote! {
+        let this = ::ferrosaur::unwrap_instance::<#self_ty>(scope, args.this())?;
+    };
+    match (&recv.reference, &recv.mutability, recv.colon_token) {
+        (Some(_), None, None) => Ok(quote! {
+            #unwrap
+            let this = &this.borrow()?;
+        }),
+        (Some(_), Some(_), None) => Ok(quote! {
+            #unwrap
+            let this = &mut this.borrow_mut()?;
+        }),
+        _ => Error::custom("method must take `&self` or `&mut self`, or no `self`")
+            .with_span(recv)
+            .pipe(Err),
+    }
+}
+
+/// Convert arguments passed from JavaScript, rewriting their types in `sig`.
+pub(crate) fn from_inputs(
+    sig: &mut Signature,
+    context: &str,
+) -> Result<(TokenStream, TokenStream)> {
+    let mut errors = Error::accumulator();
+
+    if let Some(token) = &sig.asyncness {
+        Error::custom("exported functions cannot be `async`")
+            .with_span(token)
+            .pipe(|e| errors.push(e));
+    }
+
+    let mut casts = vec![];
+    let mut args = vec![];
+
+    for (idx, arg) in sig.inputs.iter_mut().enumerate() {
+        let FnArg::Typed(arg) = arg else {
+            continue;
+        };
+
+        let conv = V8Conv::from_type((*arg.ty).clone()).and_recover(&mut errors);
+
+        if conv.is_borrowed() {
+            Error::custom("arguments cannot be borrowed, use an owned type instead")
+                .with_span(conv.as_type())
+                .pipe(|e| errors.push(e));
+        }
+
+        *arg.ty = conv.to_type();
+
+        let name = match &*arg.pat {
+            Pat::Ident(PatIdent { ident, .. }) => ident.to_string(),
+            _ => format!("arg{idx}"),
+        };
+
+        let ident = format_ident!("__arg{idx}");
+
+        let index = args.len() as i32;
+        let err = format!("failed to convert argument `{name}` of {context}");
+        let cast = conv.to_cast_from_v8(ident.to_string(), "scope");
+
+        casts.push(quote! {
+            let #ident = args.get(#index);
+            let #ident = #cast.context(#err)?;
+        });
+
+        args.push(ident);
+    }
+
+    errors.finish_with((quote! { #(#casts)* }, quote! { #(#args),* }))
+}
+
+/// Convert the return value into JavaScript, rewriting the return type in `sig`.
+///
+/// If the return type is a `Result`, `Err` is thrown as a JavaScript `Error`.
+pub(crate) fn from_output(sig: &mut Signature, context: &str) -> Result<TokenStream> {
+    let ReturnType::Type(arrow, ty) = &mut sig.output else {
+        return Ok(quote! {
+            let _: () = output;
+            Ok(v8::undefined(scope).into())
+        });
+    };
+
+    let (ty, unwrap) = match result_ok_type(ty) {
+        Some(ty) => (ty, throw_err()),
+        None => (&mut **ty, quote! {}),
+    };
+
+    if matches!(ty, Type::Tuple(tuple) if tuple.elems.is_empty()) {
+        return Ok(quote! {
+            #unwrap
+            let _: () = output;
+            Ok(v8::undefined(scope).into())
+        });
+    }
+
+    let mut errors = Error::accumulator();
+
+    let conv = V8Conv::from_output(ReturnType::Type(*arrow, Box::new(ty.clone())))
+        .and_recover(&mut errors);
+
+    *ty = conv.to_type();
+
+    let err = format!("failed to convert value returned by {context}");
+    let cast = conv.to_cast_into_v8("output", "scope");
+
+    errors.finish_with(quote! {
+        #unwrap
+        let output: v8::Local<v8::Value> = #cast.context(#err)?;
+        Ok(output)
+    })
+}
+
+/// If `ty` is a `Result`, such as `Result<T, E>` or `anyhow::Result<T>`, its `T`.
+fn result_ok_type(ty: &mut Type) -> Option<&mut Type> {
+    let Type::Path(TypePath { qself: None, path }) = ty else {
+        return None;
+    };
+    let segment = path.segments.last_mut()?;
+    if segment.ident != "Result" {
+        return None;
+    }
+    let PathArguments::AngleBracketed(args) = &mut segment.arguments else {
+        return None;
+    };
+    match args.args.first_mut()? {
+        GenericArgument::Type(ty) => Some(ty),
+        _ => None,
+    }
+}
+
+/// Replace `output` with its `Ok` value, or throw its `Err` value using its `Display` impl.
+fn throw_err() -> TokenStream {
+    quote! {
+        let output = output.map_err(|err| anyhow!("{err:#}"))?;
+    }
+}
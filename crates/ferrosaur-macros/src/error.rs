@@ -0,0 +1,188 @@
+use darling::{Error, FromMeta, Result};
+use heck::ToLowerCamelCase;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{
+    parse::{Parse, Parser},
+    spanned::Spanned,
+    Attribute, Field, Fields, ItemEnum, Variant,
+};
+use tap::Pipe;
+
+use crate::{
+    util::{
+        flag::{FlagLike, FlagName},
+        unary::Unary,
+        use_deno, use_prelude,
+        v8::V8Conv,
+        Caveat, FatalErrors, NewtypeMeta, RecoverableErrors,
+    },
+    Error_, PropKeyString,
+};
+
+#[derive(Debug, Default, Clone, FromMeta)]
+struct ErrorName {
+    name: Option<Unary<PropKeyString>>,
+}
+
+impl FlagName for ErrorName {
+    const PREFIX: &'static str = "js";
+
+    fn unit() -> Result<Self> {
+        Ok(Default::default())
+    }
+}
+
+pub fn error(_: Error_, item: TokenStream) -> Result<TokenStream> {
+    let errors = Error::accumulator();
+
+    let (mut item, mut errors) = ItemEnum::parse.parse2(item).or_fatal(errors)?;
+
+    if !item.generics.params.is_empty() || item.generics.where_clause.is_some() {
+        Error::custom("must not have generics")
+            .with_span(&item.generics)
+            .pipe(|e| errors.push(e));
+    }
+
+    let variants = item
+        .variants
+        .iter_mut()
+        .filter_map(|variant| errors.handle(from_variant(variant)))
+        .collect::<Vec<_>>();
+
+    let ident = &item.ident;
+
+    errors.finish()?;
+
+    Ok(quote! {
+        #item
+
+        const _: () = {
+            #use_prelude
+            #use_deno
+
+            #[automatically_derived]
+            impl ::ferrosaur::FromException for #ident {
+                fn from_exception<'a>(
+                    scope: &mut v8::HandleScope<'a>,
+                    exception: v8::Local<'a, v8::Value>,
+                ) -> Result<Option<Self>> {
+                    let Ok(object) = exception.try_cast::<v8::Object>() else {
+                        return Ok(None);
+                    };
+
+                    for name in ::ferrosaur::exception_names(scope, object)? {
+                        let name = name.as_str();
+                        #(#variants)*
+                    }
+
+                    Ok(None)
+                }
+            }
+        };
+    })
+}
+
+fn from_variant(variant: &mut Variant) -> Result<TokenStream> {
+    let mut errors = Error::accumulator();
+
+    let name = take_name(&mut variant.attrs, &variant.ident)
+        .and_recover(&mut errors)
+        .unwrap_or_else(|| variant.ident.to_string());
+
+    let ident = &variant.ident;
+
+    let init = match &mut variant.fields {
+        Fields::Unit => quote! { Self::#ident },
+
+        Fields::Named(fields) => {
+            let fields = fields
+                .named
+                .iter_mut()
+                .filter_map(|field| errors.handle(from_field(field, &name)))
+                .collect::<Vec<_>>();
+            quote! { Self::#ident { #(#fields,)* } }
+        }
+
+        Fields::Unnamed(fields) => {
+            if fields.unnamed.len() == 1 {
+                let field = &mut fields.unnamed[0];
+                let ty = V8Conv::from_type(field.ty.clone()).and_recover(&mut errors);
+                field.ty = ty.to_type();
+                let data = ty.to_cast_from_v8("exception", "scope");
+                quote! {
+                    Self::#ident(
+                        #data.with_context(|| format!("failed to convert {}", #name))?
+                    )
+                }
+            } else {
+                "expected exactly one field, which receives the thrown value"
+                    .pipe(Error::custom)
+                    .with_span(&fields)
+                    .pipe(|e| errors.push(e));
+                quote! {}
+            }
+        }
+    };
+
+    errors.finish()?;
+
+    Ok(quote! {
+        if name == #name {
+            return Ok(Some(#init));
+        }
+    })
+}
+
+fn from_field(field: &mut Field, error: &str) -> Result<TokenStream> {
+    let mut errors = Error::accumulator();
+
+    let name = take_name(&mut field.attrs, &field.ident).and_recover(&mut errors);
+
+    let Some(ident) = &field.ident else {
+        return Err(Error::custom("expected a named field").with_span(field));
+    };
+
+    let key = name.unwrap_or_else(|| ident.to_string().to_lower_camel_case());
+
+    let ty = V8Conv::from_type(field.ty.clone()).and_recover(&mut errors);
+    field.ty = ty.to_type();
+
+    let data = ty.to_cast_from_v8("data", "scope");
+
+    let err = format!("failed to convert property {key:?} of {error}");
+
+    errors.finish()?;
+
+    Ok(quote! {
+        #ident: {
+            let key = FastString::from_static(#key).v8_string(scope)?;
+            let data = object
+                .get(scope, key.into())
+                .context(#err)?;
+            #data.context(#err)?
+        }
+    })
+}
+
+fn take_name<S: Spanned>(attrs: &mut Vec<Attribute>, span: S) -> Caveat<Option<String>> {
+    let mut errors = Error::accumulator();
+
+    let (items, rest) =
+        FlagLike::<ErrorName>::collect(std::mem::take(attrs)).and_recover(&mut errors);
+
+    *attrs = rest;
+
+    if items.len() > 1 {
+        Error::custom("expected at most one #[js(name = ...)]")
+            .with_span(&span)
+            .pipe(|e| errors.push(e));
+    }
+
+    let name = items
+        .into_iter()
+        .next()
+        .and_then(|FlagLike(ErrorName { name })| name.into_inner().into_inner());
+
+    (name, errors.finish().err()).into()
+}
@@ -0,0 +1,203 @@
+use darling::{Error, Result};
+use heck::ToLowerCamelCase;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{
+    parse::{Parse, Parser},
+    Item, ItemFn, ItemImpl, ItemMod,
+};
+use tap::Pipe;
+
+use crate::{
+    class::{from_inputs, from_output},
+    util::{
+        flag::FlagLike, unary::Unary, use_deno, use_prelude, Caveat, FatalErrors, NewtypeMeta,
+        RecoverableErrors,
+    },
+    Export, HostModule, JsItem,
+};
+
+pub fn host_module(
+    HostModule(Unary(specifier)): HostModule,
+    item: TokenStream,
+) -> Result<TokenStream> {
+    let errors = Error::accumulator();
+
+    let (item, mut errors) = ItemMod::parse.parse2(item).or_fatal(errors)?;
+
+    let ItemMod {
+        attrs,
+        vis,
+        unsafety,
+        mod_token,
+        ident,
+        content,
+        semi,
+    } = item;
+
+    let Some((_, mut items)) = content else {
+        return "expected an inline module\nexported fns must be declared in the module body"
+            .pipe(Error::custom)
+            .with_span(&semi)
+            .pipe(Err);
+    };
+
+    let mut exports = vec![];
+
+    for item in items.iter_mut() {
+        match item {
+            Item::Fn(func) => {
+                let Some(export) = take_export(func).and_recover(&mut errors) else {
+                    continue;
+                };
+                if let Some(export) = errors.handle(export_fn(export, func, &specifier)) {
+                    exports.push(export);
+                }
+            }
+            Item::Impl(item) if is_exported_class(item) => {
+                let self_ty = &item.self_ty;
+                exports.push((
+                    quote! { <#self_ty as ::ferrosaur::JsClass>::NAME },
+                    quote! { ::ferrosaur::class_constructor::<#self_ty>(scope)? },
+                ));
+            }
+            _ => {}
+        }
+    }
+
+    let (names, funcs): (Vec<_>, Vec<_>) = exports.into_iter().unzip();
+
+    let op = specifier
+        .chars()
+        .map(|ch| if ch.is_ascii_alphanumeric() { ch } else { '_' })
+        .collect::<String>()
+        .pipe(|name| format_ident!("op_host_module_{name}"));
+
+    errors.finish()?;
+
+    Ok(quote! {
+        #(#attrs)*
+        #vis #unsafety #mod_token #ident {
+            #(#items)*
+
+            /// The specifier that other modules import this module from.
+            pub const SPECIFIER: &str = #specifier;
+
+            /// Create an extension that provides this module, so that other modules can
+            /// import it from [`SPECIFIER`].
+            ///
+            /// Add it to [`RuntimeOptions::extensions`][deno_core::RuntimeOptions::extensions]
+            /// when creating the runtime.
+            pub fn extension() -> deno_core::Extension {
+                #use_prelude
+                #use_deno
+
+                fn exports<'s>(
+                    scope: &mut v8::HandleScope<'s>,
+                ) -> Result<v8::Local<'s, v8::Value>> {
+                    let funcs: &[v8::Local<v8::Value>] = &[#(#funcs.into()),*];
+                    Ok(v8::Array::new_with_elements(scope, funcs).into())
+                }
+
+                #[deno_core::op2]
+                fn #op<'s>(scope: &mut v8::HandleScope<'s>) -> v8::Local<'s, v8::Value> {
+                    match exports(scope) {
+                        Ok(exports) => exports,
+                        Err(error) => ::ferrosaur::throw_host_module_error(scope, error),
+                    }
+                }
+
+                ::ferrosaur::host_module_extension(SPECIFIER, #op(), &[#(#names),*])
+            }
+        }
+    })
+}
+
+/// Take `#[js(export)]` out of the attributes of `func`.
+fn take_export(func: &mut ItemFn) -> Caveat<Option<Export>> {
+    let mut errors = Error::accumulator();
+
+    let (items, rest) =
+        FlagLike::<JsItem>::collect(std::mem::take(&mut func.attrs)).and_recover(&mut errors);
+
+    func.attrs = rest;
+
+    let mut export = None;
+
+    items.into_iter().for_each(|FlagLike(item)| match item {
+        JsItem::Export(FlagLike(item)) if export.is_none() => export = Some(item),
+        JsItem::Export(_) => Error::custom("duplicate #[js(export)]")
+            .with_span(&func.sig.ident)
+            .pipe(|e| errors.push(e)),
+        _ => Error::custom("only #[js(export)] is supported on fns here")
+            .with_span(&func.sig.ident)
+            .pipe(|e| errors.push(e)),
+    });
+
+    (export, errors.finish().err()).into()
+}
+
+/// Whether `item` is the `#[js(export)]` impl of a `#[js(class)]`.
+fn is_exported_class(item: &ItemImpl) -> bool {
+    let Caveat((items, _), _) = FlagLike::<JsItem>::collect(item.attrs.clone());
+    items
+        .iter()
+        .any(|FlagLike(item)| matches!(item, JsItem::Export(_)))
+}
+
+/// Derive a JavaScript function that calls `func`, and its export name.
+fn export_fn(
+    Export { name }: Export,
+    func: &mut ItemFn,
+    specifier: &str,
+) -> Result<(TokenStream, TokenStream)> {
+    let mut errors = Error::accumulator();
+
+    let ident = func.sig.ident.clone();
+
+    let name = name
+        .into_inner()
+        .into_inner()
+        .unwrap_or_else(|| ident.to_string().to_lower_camel_case());
+
+    if let Some(recv) = func.sig.receiver() {
+        Error::custom("exported fns cannot take `self`")
+            .with_span(recv)
+            .pipe(|e| errors.push(e));
+    }
+
+    if !func.sig.generics.params.is_empty() || func.sig.generics.where_clause.is_some() {
+        Error::custom("exported fns cannot have generics")
+            .with_span(&func.sig.generics)
+            .pipe(|e| errors.push(e));
+    }
+
+    let context = format!("{name} from {specifier:?}");
+
+    let (casts, args) = errors.handle(from_inputs(&mut func.sig, &context)).unzip();
+
+    let output = errors.handle(from_output(&mut func.sig, &context));
+
+    errors.finish()?;
+
+    let func = quote! {{
+        struct Export;
+
+        impl ::ferrosaur::Callback for Export {
+            fn call<'s>(
+                &self,
+                scope: &mut v8::HandleScope<'s>,
+                args: &v8::FunctionCallbackArguments<'s>,
+            ) -> Result<v8::Local<'s, v8::Value>> {
+                let _ = args;
+                #casts
+                let output = self::#ident(#args);
+                #output
+            }
+        }
+
+        ::ferrosaur::new_callback(scope, Export)?
+    }};
+
+    Ok((quote! { #name }, func))
+}
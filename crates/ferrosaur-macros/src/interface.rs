@@ -15,21 +15,41 @@ use crate::{
         interface::{DeriveInterface, InterfaceLike, OuterType, SomeFunc, SomeType},
         no_default_fn, no_fn_body,
         property::PropertyKey,
+        site::track_caller,
         string::StringLike,
+        unary::Unary,
         Caveat, FatalErrors,
     },
-    Constructor, Function, Getter, Interface, JsProp, PropKeyString, PropKeySymbol, Property,
-    Setter,
+    Constructor, EventLoopOptions, Function, Getter, Interface, JsProp, PropKeyString,
+    PropKeySymbol, Property, Setter,
 };
 
 mod func;
+mod host;
 mod index;
 mod prop;
 
-pub fn interface(_: Interface, item: TokenStream) -> Result<TokenStream> {
-    InterfaceLike::parse
-        .parse2(item)?
-        .derive::<DeriveProperties>()
+pub fn interface(Interface { host }: Interface, item: TokenStream) -> Result<TokenStream> {
+    let item = InterfaceLike::parse.parse2(item)?;
+
+    let Some(Unary(host)) = host else {
+        return item.derive(&DeriveProperties);
+    };
+
+    let InterfaceLike::Trait(trait_) = &item else {
+        return "`host(...)` is only supported on traits"
+            .pipe(Error::custom)
+            .pipe(Err);
+    };
+
+    let mut errors = Error::accumulator();
+
+    let host = errors.handle(host::host_trait(host, trait_));
+    let item = errors.handle(item.derive(&DeriveProperties));
+
+    errors.finish()?;
+
+    Ok(quote! { #item #host })
 }
 
 struct DeriveProperties;
@@ -79,6 +99,8 @@ impl DeriveInterface for DeriveProperties {
         let ((FlagLike(prop), attrs), errors) =
             FlagLike::<JsProp>::exactly_one(attrs, sig.ident.span()).or_fatal(errors)?;
 
+        let track_caller = track_caller(&sig);
+
         let (impl_, errors) = match prop {
             JsProp::Prop(FlagLike(prop)) => {
                 prop::impl_property(prop, sig).error_at::<JsProp, Property>()
@@ -102,7 +124,7 @@ impl DeriveInterface for DeriveProperties {
 
         let impl_ = impl_
             .into_iter()
-            .map(|impl_| quote! { #(#attrs)* #vis #impl_ });
+            .map(|impl_| quote! { #(#attrs)* #track_caller #vis #impl_ });
 
         Ok(quote! { #(#impl_)* })
     }
@@ -121,7 +143,7 @@ impl DeriveInterface for DeriveProperties {
         Self::unsupported(item)
     }
 
-    fn derive_type(item: SomeType, _: OuterType) -> Result<TokenStream> {
+    fn derive_type(&self, item: SomeType, _: OuterType) -> Result<TokenStream> {
         Self::unsupported(item.ident)
     }
 }
@@ -5,12 +5,13 @@ use tap::{Pipe, Tap};
 
 use crate::util::{
     expect_self_arg,
-    function::{CallFunction, FunctionIntent},
+    function::{CallFunction, EventLoopPolicy, FunctionIntent},
     property::PropertyKey,
+    ty::TypeLike,
     Caveat, MergeErrors, NewtypeMeta, RecoverableErrors,
 };
 
-use super::{property_key, Constructor, Function, ResolveName};
+use super::{property_key, Constructor, EventLoopOptions, Function, ResolveName};
 
 pub enum Callable {
     Func(Function),
@@ -46,7 +47,15 @@ impl From<Constructor> for Callable {
     }
 }
 
-fn func_to_call(Function { name, symbol }: Function, sig: &mut Signature) -> Caveat<CallFunction> {
+fn func_to_call(func: Function, sig: &mut Signature) -> Caveat<CallFunction> {
+    let Function {
+        name,
+        symbol,
+        throws,
+        event_loop,
+        timeout_ms,
+    } = func;
+
     let mut errors = Error::accumulator();
 
     let name = ResolveName {
@@ -59,16 +68,67 @@ fn func_to_call(Function { name, symbol }: Function, sig: &mut Signature) -> Cav
 
     let call = CallFunction::from_sig(sig)
         .and_recover(&mut errors)
-        .tap_mut(|call| call.source = name.into());
+        .tap_mut(|call| call.source = name.into())
+        .tap_mut(|call| call.throws = throws.into_inner().map(|TypeLike(ty)| *ty))
+        .tap_mut(|call| call.timeout = timeout_ms);
+
+    let call = match event_loop {
+        None => call,
+        Some(options) => {
+            let event_loop = event_loop_policy(options, sig).and_recover(&mut errors);
+            call.tap_mut(|call| call.event_loop = event_loop)
+        }
+    };
 
     (call, errors.into_one()).into()
 }
 
+fn event_loop_policy(options: EventLoopOptions, sig: &Signature) -> Caveat<EventLoopPolicy> {
+    let EventLoopOptions {
+        wait_for_inspector,
+        pump_v8_message_loop,
+        none,
+    } = options;
+
+    let mut errors = Error::accumulator();
+
+    if sig.asyncness.is_none() {
+        "`event_loop(...)` only applies to `async` functions"
+            .pipe(Error::custom)
+            .with_span(&sig.fn_token)
+            .pipe(|e| errors.push(e));
+    }
+
+    let policy = if none.is_present() {
+        if wait_for_inspector.is_some() || pump_v8_message_loop.is_some() {
+            "`none` does not run the event loop, and cannot be used with other options"
+                .pipe(Error::custom)
+                .with_span(&none.span())
+                .pipe(|e| errors.push(e));
+        }
+        EventLoopPolicy::None
+    } else {
+        EventLoopPolicy::Poll {
+            wait_for_inspector,
+            pump_v8_message_loop,
+        }
+    };
+
+    (policy, errors.into_one()).into()
+}
+
 fn ctor_to_call(Constructor { class }: Constructor, sig: &mut Signature) -> Caveat<CallFunction> {
     let mut errors = Error::accumulator();
 
     let mut call = CallFunction::from_sig(sig).and_recover(&mut errors);
 
+    if let FunctionIntent::Deferred = call.intent {
+        "constructor cannot return `JsPromise<...>`"
+            .pipe(Error::custom)
+            .with_span(&sig.output)
+            .pipe(|e| errors.push(e));
+    }
+
     let name = match (class.into_inner().into_inner(), &call.output) {
         (Some(class), _) => PropertyKey::String(class),
 
@@ -0,0 +1,290 @@
+use darling::{Error, Result};
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{
+    parse_quote, Ident, ItemTrait, ReturnType, Signature, TraitItem, TraitItemFn, Type, TypePath,
+};
+use tap::Pipe;
+
+use crate::{
+    class::{from_inputs, from_output},
+    util::{
+        expect_self_arg, flag::FlagLike, inner_mod_name, ty::TypeLike, use_deno, use_prelude,
+        FatalErrors, NewtypeMeta, RecoverableErrors,
+    },
+    Function, JsProp, Property,
+};
+
+use super::ResolveName;
+
+/// Derive a trait that Rust types implement to be passed to JavaScript as `trait_`.
+pub fn host_trait(TypeLike(host): TypeLike, trait_: &ItemTrait) -> Result<TokenStream> {
+    let mut errors = Error::accumulator();
+
+    let host = match &*host {
+        Type::Path(TypePath { qself: None, path }) if path.get_ident().is_some() => {
+            path.get_ident().cloned().unwrap()
+        }
+        ty => {
+            return Error::custom("expected an identifier for the host trait")
+                .with_span(ty)
+                .pipe(Err)
+        }
+    };
+
+    if !trait_.generics.params.is_empty() || trait_.generics.where_clause.is_some() {
+        "traits with generics cannot have a host trait"
+            .pipe(Error::custom)
+            .with_span(&trait_.generics)
+            .pipe(|e| errors.push(e));
+    }
+
+    let mut decls = vec![];
+    let mut members = vec![];
+
+    for item in trait_.items.iter() {
+        let TraitItem::Fn(item) = item else {
+            continue;
+        };
+        if let Some((decl, member)) = errors.handle(host_member(item, &host)) {
+            decls.push(decl);
+            members.push(member);
+        }
+    }
+
+    errors.finish()?;
+
+    let vis = &trait_.vis;
+    let ident = &trait_.ident;
+    let inner_mod = inner_mod_name("host", &host);
+
+    let doc = format!(
+        "Implement [`{ident}`] in Rust.\n\nUse [`{host}::into_js`] to pass the implementation to JavaScript."
+    );
+
+    Ok(quote! {
+        #[doc(inline)]
+        #[allow(unused_imports)]
+        #vis use #inner_mod::#host;
+
+        #[doc(hidden)]
+        mod #inner_mod {
+            #[allow(unused)]
+            use super::*;
+            #use_prelude
+            #use_deno
+
+            #[doc = #doc]
+            pub trait #host: 'static {
+                #(#decls)*
+
+                /// Create a JavaScript object whose properties call into this value.
+                fn into_js(self, rt: &mut JsRuntime) -> ::ferrosaur::Result<v8::Global<v8::Object>>
+                where
+                    Self: Sized,
+                {
+                    let host = _alloc::rc::Rc::new(self);
+                    let scope = &mut rt.handle_scope();
+                    let object = v8::Object::new(scope);
+                    #(#members)*
+                    Ok(v8::Global::new(scope, object))
+                }
+            }
+        }
+    })
+}
+
+/// Derive the declaration of a host fn, and the code to define it on the host object.
+fn host_member(item: &TraitItemFn, host: &Ident) -> Result<(TokenStream, TokenStream)> {
+    let errors = Error::accumulator();
+
+    let TraitItemFn { attrs, sig, .. } = item;
+
+    let ((FlagLike(prop), attrs), mut errors) =
+        FlagLike::<JsProp>::exactly_one(attrs.clone(), sig.ident.span()).or_fatal(errors)?;
+
+    errors.handle(expect_self_arg(&sig.inputs, &sig.ident));
+
+    if !sig.generics.params.is_empty() || sig.generics.where_clause.is_some() {
+        Error::custom("fn with generics cannot be implemented by a host")
+            .with_span(&sig.generics)
+            .pipe(|e| errors.push(e));
+    }
+
+    let ((decl, member), errors) = match prop {
+        JsProp::Func(FlagLike(func)) => host_func(func, sig.clone(), host),
+        JsProp::Prop(FlagLike(prop)) => host_prop(prop, sig.clone(), host),
+        _ => "only #[js(func)] and #[js(prop)] can be implemented by a host"
+            .pipe(Error::custom)
+            .with_span(&sig.ident)
+            .pipe(Err),
+    }
+    .or_fatal(errors)?;
+
+    errors.finish_with((quote! { #(#attrs)* #decl }, member))
+}
+
+fn host_func(
+    func: Function,
+    mut sig: Signature,
+    host: &Ident,
+) -> Result<(TokenStream, TokenStream)> {
+    let mut errors = Error::accumulator();
+
+    let Function { name, symbol, .. } = func;
+
+    let ident = sig.ident.clone();
+
+    let name = ResolveName {
+        ident: &ident,
+        name: name.into_inner(),
+        symbol: symbol.into_inner(),
+    }
+    .resolve()
+    .and_recover(&mut errors);
+
+    let context = format!("host function `{ident}`");
+
+    let (casts, args) = errors.handle(from_inputs(&mut sig, &context)).unzip();
+    let output = errors.handle(from_output(&mut sig, &context));
+
+    errors.finish()?;
+
+    let func = define_callback(
+        host,
+        quote! {
+            #casts
+            let output = T::#ident(&self.0, #args);
+            #output
+        },
+    );
+
+    Ok((
+        quote! { #sig; },
+        quote! {{
+            #func
+            let key = #name;
+            object
+                .set(scope, key.into(), func.into())
+                .ok_or_else(|| ::ferrosaur::Error::other(anyhow!("failed to define host function")))?;
+        }},
+    ))
+}
+
+fn host_prop(prop: Property, sig: Signature, host: &Ident) -> Result<(TokenStream, TokenStream)> {
+    let mut errors = Error::accumulator();
+
+    let Property {
+        name,
+        symbol,
+        with_setter,
+    } = prop;
+
+    let ident = sig.ident.clone();
+
+    let name = ResolveName {
+        ident: &ident,
+        name: name.into_inner(),
+        symbol: symbol.into_inner(),
+    }
+    .resolve()
+    .and_recover(&mut errors);
+
+    if sig.inputs.len() > 1 {
+        Error::custom("must not have extra arguments")
+            .with_span(&sig.inputs.get(1))
+            .pipe(|e| errors.push(e));
+    }
+
+    let ReturnType::Type(_, ty) = &sig.output else {
+        return Err(Error::custom("must have a return type").with_span(&sig.ident));
+    };
+
+    let mut getter: Signature = parse_quote! { fn #ident(&self) -> #ty };
+
+    let getter_context = format!("host getter `{ident}`");
+    let get_output = errors.handle(from_output(&mut getter, &getter_context));
+
+    let get = define_callback(
+        host,
+        quote! {
+            let output = T::#ident(&self.0);
+            #get_output
+        },
+    );
+
+    let mut decls = vec![quote! { #getter; }];
+
+    let set = if with_setter.is_present() {
+        let setter_ident = format_ident!("set_{ident}");
+        let mut setter: Signature = parse_quote! { fn #setter_ident(&self, #ident: #ty) };
+
+        let setter_context = format!("host setter `{ident}`");
+        let (casts, args) = errors
+            .handle(from_inputs(&mut setter, &setter_context))
+            .unzip();
+        let set_output = errors.handle(from_output(&mut setter, &setter_context));
+
+        decls.push(quote! { #setter; });
+
+        let set = define_callback(
+            host,
+            quote! {
+                #casts
+                let output = T::#setter_ident(&self.0, #args);
+                #set_output
+            },
+        );
+
+        quote! {
+            let set = {
+                #set
+                func
+            };
+        }
+    } else {
+        quote! {
+            let set = v8::undefined(scope);
+        }
+    };
+
+    errors.finish()?;
+
+    Ok((
+        quote! { #(#decls)* },
+        quote! {{
+            let get = {
+                #get
+                func
+            };
+            #set
+            let key = #name;
+            let mut desc = v8::PropertyDescriptor::new_from_get_set(get.into(), set.into());
+            desc.set_enumerable(true);
+            object
+                .define_property(scope, key.into(), &desc)
+                .ok_or_else(|| ::ferrosaur::Error::other(anyhow!("failed to define host property")))?;
+        }},
+    ))
+}
+
+/// Create a `v8::Function` named `func` that runs `body`, in which `self.0` is the host.
+fn define_callback(host: &Ident, body: TokenStream) -> TokenStream {
+    quote! {
+        struct Func<T>(_alloc::rc::Rc<T>);
+
+        impl<T: #host> ::ferrosaur::Callback for Func<T> {
+            fn call<'s>(
+                &self,
+                scope: &mut v8::HandleScope<'s>,
+                args: &v8::FunctionCallbackArguments<'s>,
+            ) -> Result<v8::Local<'s, v8::Value>> {
+                let _ = args;
+                #body
+            }
+        }
+
+        let func = ::ferrosaur::new_callback(scope, Func(host.clone()))
+            .map_err(::ferrosaur::Error::other)?;
+    }
+}
@@ -7,7 +7,7 @@ use tap::Pipe;
 
 use crate::util::{
     expect_self_arg, function::FunctionIntent, only_explicit_return_type, only_pat_ident,
-    v8::V8Conv, FatalErrors, RecoverableErrors,
+    site::CallSite, v8::V8Conv, FatalErrors, RecoverableErrors,
 };
 
 use super::{Getter, Setter};
@@ -45,8 +45,8 @@ pub fn impl_getter(_: Getter, sig: Signature) -> Result<Vec<TokenStream>> {
 
     let getter = {
         let getter = val_type.to_getter(&generics);
-        let from_key = key_name.map(ToString::to_string).unwrap_or_default();
-        let from_key = key_type.to_cast_into_v8(from_key, "scope");
+        let key = key_name.map(ToString::to_string).unwrap_or_default();
+        let from_key = key_type.to_cast_into_v8(&key, "scope");
         let key_type = key_type.as_type();
         let val_type = val_type.to_type();
 
@@ -56,21 +56,37 @@ pub fn impl_getter(_: Getter, sig: Signature) -> Result<Vec<TokenStream>> {
             ..
         } = generics;
 
+        let return_ty = quote! { ::ferrosaur::Result<#val_type> };
+
+        let site = CallSite {
+            binding: &ident,
+            path: None,
+            asyncness: false,
+        };
+
+        let body = site.wrap(
+            &return_ty,
+            quote! {
+                #getter
+                let scope = &mut rt.handle_scope();
+                let this = ToV8::to_v8(self, scope)
+                    .map_err(::ferrosaur::Error::other)?;
+                let this = v8::Local::new(scope, this);
+                let prop: v8::Local<v8::Value> = #from_key
+                    .map_err(|err| ::ferrosaur::Error::argument(0, #key, err))?;
+                getter(scope, this, prop)
+            },
+        );
+
         quote! {
             fn #ident <#params> (
                 #self_arg,
                 #key_name: #key_type,
                 rt: &mut JsRuntime,
-            ) -> Result<#val_type>
+            ) -> #return_ty
             #where_clause
             {
-                #getter
-                let scope = &mut rt.handle_scope();
-                let this = ToV8::to_v8(self, scope)?;
-                let this = v8::Local::new(scope, this);
-                let prop: v8::Local<v8::Value> = #from_key?;
-                getter(scope, this, prop)
-                    .context("failed to index into object")
+                #body
             }
         }
     };
@@ -117,29 +133,42 @@ pub fn impl_setter(_: Setter, sig: Signature) -> Result<Vec<TokenStream>> {
     let val_type = V8Conv::from_fn_arg(inputs[2].clone()).and_recover(&mut errors);
 
     let setter = {
-        let setter = val_type.to_setter(&generics);
-        let from_key = key_name.map(ToString::to_string).unwrap_or_default();
-        let from_key = key_type.to_cast_into_v8(from_key, "scope");
+        let val = val_name.map(ToString::to_string).unwrap_or_default();
+        let setter = val_type.to_setter(&generics, 1, &val);
+        let key = key_name.map(ToString::to_string).unwrap_or_default();
+        let from_key = key_type.to_cast_into_v8(&key, "scope");
         let key_type = key_type.to_type();
         let val_type = val_type.to_type();
         let params = &generics.params;
         let where_ = &generics.where_clause;
+        let site = CallSite {
+            binding: &ident,
+            path: None,
+            asyncness: false,
+        };
+        let body = site.wrap(
+            quote! { ::ferrosaur::Result<()> },
+            quote! {
+                #setter
+                let scope = &mut _rt.handle_scope();
+                let this = ToV8::to_v8(self, scope)
+                    .map_err(::ferrosaur::Error::other)?;
+                let this = v8::Local::new(scope, this);
+                let prop: v8::Local<v8::Value> = #from_key
+                    .map_err(|err| ::ferrosaur::Error::argument(0, #key, err))?;
+                setter(scope, this, prop, #val_name)
+            },
+        );
         quote! {
             fn #ident <#params> (
                 #self_arg,
                 #key_name: #key_type,
                 #val_name: #val_type,
                 _rt: &mut JsRuntime,
-            ) -> Result<&Self>
+            ) -> ::ferrosaur::Result<&Self>
             #where_
             {
-                #setter
-                let scope = &mut _rt.handle_scope();
-                let this = ToV8::to_v8(self, scope)?;
-                let this = v8::Local::new(scope, this);
-                let prop: v8::Local<v8::Value> = #from_key?;
-                setter(scope, this, prop, #val_name)
-                    .context("failed to set property")?;
+                let () = { #body }?;
                 Ok(self)
             }
         }
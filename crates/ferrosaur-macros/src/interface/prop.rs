@@ -5,8 +5,8 @@ use syn::Signature;
 use tap::Pipe;
 
 use crate::util::{
-    expect_self_arg, function::FunctionIntent, only_explicit_return_type, v8::V8Conv, NewtypeMeta,
-    RecoverableErrors,
+    expect_self_arg, function::FunctionIntent, only_explicit_return_type, site::CallSite,
+    v8::V8Conv, NewtypeMeta, RecoverableErrors,
 };
 
 use super::{Property, ResolveName};
@@ -52,34 +52,64 @@ pub fn impl_property(prop: Property, sig: Signature) -> Result<Vec<TokenStream>>
 
     let return_ty = V8Conv::from_output(output).and_recover(&mut errors);
 
+    let path = name.to_name();
+
     let getter = {
         let getter = return_ty.to_getter(&generics);
         let return_ty = return_ty.to_type();
-        let err = format!("failed to get property {name:?}");
+        let return_ty = quote! { ::ferrosaur::Result<#return_ty> };
+        let site = CallSite {
+            binding: &ident,
+            path: Some(path.clone()),
+            asyncness: false,
+        };
+        let body = site.wrap(
+            &return_ty,
+            quote! {
+                #getter
+                let scope = &mut rt.handle_scope();
+                let this = ToV8::to_v8(self, scope)
+                    .map_err(::ferrosaur::Error::other)?;
+                let this = v8::Local::new(scope, this);
+                let prop = #name;
+                getter(scope, this, prop)
+            },
+        );
         let params = &generics.params;
         let where_ = &generics.where_clause;
         quote! {
             fn #ident <#params> (
                 #self_arg,
                 rt: &mut JsRuntime,
-            ) -> Result<#return_ty>
+            ) -> #return_ty
             #where_
             {
-                #getter
-                let scope = &mut rt.handle_scope();
-                let this = ToV8::to_v8(self, scope)?;
-                let this = v8::Local::new(scope, this);
-                let prop = #name;
-                getter(scope, this, prop).context(#err)
+                #body
             }
         }
     };
 
     let setter = if with_setter.is_present() {
         let ident = format_ident!("set_{}", ident);
-        let setter = return_ty.to_setter(&generics);
+        let setter = return_ty.to_setter(&generics, 0, "data");
         let data_type = return_ty.to_type();
-        let err = format!("failed to set property {name:?}");
+        let site = CallSite {
+            binding: &ident,
+            path: Some(path),
+            asyncness: false,
+        };
+        let body = site.wrap(
+            quote! { ::ferrosaur::Result<()> },
+            quote! {
+                #setter
+                let scope = &mut _rt.handle_scope();
+                let this = ToV8::to_v8(self, scope)
+                    .map_err(::ferrosaur::Error::other)?;
+                let this = v8::Local::new(scope, this);
+                let prop = #name;
+                setter(scope, this, prop, data)
+            },
+        );
         let params = &generics.params;
         let where_ = &generics.where_clause;
         quote! {
@@ -87,15 +117,10 @@ pub fn impl_property(prop: Property, sig: Signature) -> Result<Vec<TokenStream>>
                 #self_arg,
                 data: #data_type,
                 _rt: &mut JsRuntime,
-            ) -> Result<&Self>
+            ) -> ::ferrosaur::Result<&Self>
             #where_
             {
-                #setter
-                let scope = &mut _rt.handle_scope();
-                let this = ToV8::to_v8(self, scope)?;
-                let this = v8::Local::new(scope, this);
-                let prop = #name;
-                setter(scope, this, prop, data).context(#err)?;
+                let () = { #body }?;
                 Ok(self)
             }
         }
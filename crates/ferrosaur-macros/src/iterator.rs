@@ -0,0 +1,1108 @@
+use darling::{Error, Result};
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote, ToTokens};
+use syn::{
+    parse::{Parse, Parser},
+    punctuated::Punctuated,
+    spanned::Spanned,
+    Attribute, Generics, Ident, ImplItemFn, ImplItemType, Token, TraitItemFn, TraitItemType,
+    TypeParamBound, Visibility,
+};
+use tap::Pipe;
+
+use crate::{
+    util::{
+        function::{BindFunction, FunctionLength, FunctionThis},
+        interface::{DeriveInterface, InterfaceLike, OuterType, OuterTypeKind, SomeFunc, SomeType},
+        property::{PropertyKey, WellKnown},
+        site::CallSite,
+        type_ident,
+        v8::{to_v8_bound, V8Conv},
+        FatalErrors, MergeGenerics, RecoverableErrors,
+    },
+    AsyncIterator, Generator, Iterable, Iterator_,
+};
+
+pub fn iterator(Iterator_ { batch }: Iterator_, item: TokenStream) -> Result<TokenStream> {
+    if batch == Some(0) {
+        return "batch size must be at least 1"
+            .pipe(Error::custom)
+            .pipe(Err);
+    }
+    InterfaceLike::parse
+        .parse2(item)?
+        .derive(&DeriveIterator { batch })
+}
+
+pub fn async_iterator(_: AsyncIterator, item: TokenStream) -> Result<TokenStream> {
+    InterfaceLike::parse
+        .parse2(item)?
+        .derive(&DeriveAsyncIterator)
+}
+
+pub fn iterable(_: Iterable, item: TokenStream) -> Result<TokenStream> {
+    InterfaceLike::parse.parse2(item)?.derive(&DeriveIterable)
+}
+
+pub fn generator(_: Generator, item: TokenStream) -> Result<TokenStream> {
+    InterfaceLike::parse.parse2(item)?.derive(&DeriveGenerator)
+}
+
+struct DeriveIterator {
+    batch: Option<usize>,
+}
+
+struct DeriveAsyncIterator;
+
+struct DeriveIterable;
+
+struct DeriveGenerator;
+
+impl DeriveInterface for DeriveIterator {
+    fn impl_type(item: ImplItemType) -> Result<SomeType> {
+        type_named_item(&item.ident)?;
+        impl_assoc_type(item)
+    }
+
+    fn trait_type(item: TraitItemType) -> Result<SomeType> {
+        type_named_item(&item.ident)?;
+        trait_assoc_type(item)
+    }
+
+    fn count_items(fns: usize, types: usize) -> Result<()> {
+        match (fns, types) {
+            (_, 1) => Ok(()),
+            (_, _) => "expected exactly one `type Item = ...`"
+                .pipe(Error::custom)
+                .pipe(Err),
+        }
+    }
+
+    fn derive_type(&self, item: SomeType, this: OuterType) -> Result<TokenStream> {
+        derive_iterator(item, this, false, self.batch)
+    }
+
+    fn unsupported<T, S: Spanned>(span: S) -> Result<T> {
+        Error::custom("unexpected item\nmove this item to another impl block")
+            .with_span(&span)
+            .pipe(Err)
+    }
+
+    fn impl_func(item: ImplItemFn) -> Result<SomeFunc> {
+        Self::unsupported(item)
+    }
+
+    fn trait_func(item: TraitItemFn) -> Result<SomeFunc> {
+        Self::unsupported(item)
+    }
+
+    fn derive_func(item: SomeFunc, _: OuterType) -> Result<TokenStream> {
+        Self::unsupported(item.sig.ident)
+    }
+}
+
+impl DeriveInterface for DeriveAsyncIterator {
+    fn impl_type(item: ImplItemType) -> Result<SomeType> {
+        DeriveIterator::impl_type(item)
+    }
+
+    fn trait_type(item: TraitItemType) -> Result<SomeType> {
+        DeriveIterator::trait_type(item)
+    }
+
+    fn count_items(fns: usize, types: usize) -> Result<()> {
+        DeriveIterator::count_items(fns, types)
+    }
+
+    fn derive_type(&self, item: SomeType, this: OuterType) -> Result<TokenStream> {
+        derive_iterator(item, this, true, None)
+    }
+
+    fn unsupported<T, S: Spanned>(span: S) -> Result<T> {
+        DeriveIterator::unsupported(span)
+    }
+
+    fn impl_func(item: ImplItemFn) -> Result<SomeFunc> {
+        DeriveIterator::impl_func(item)
+    }
+
+    fn trait_func(item: TraitItemFn) -> Result<SomeFunc> {
+        DeriveIterator::trait_func(item)
+    }
+
+    fn derive_func(item: SomeFunc, this: OuterType) -> Result<TokenStream> {
+        DeriveIterator::derive_func(item, this)
+    }
+}
+
+impl DeriveInterface for DeriveIterable {
+    fn impl_type(item: ImplItemType) -> Result<SomeType> {
+        DeriveIterator::impl_type(item)
+    }
+
+    fn trait_type(item: TraitItemType) -> Result<SomeType> {
+        DeriveIterator::trait_type(item)
+    }
+
+    fn count_items(fns: usize, types: usize) -> Result<()> {
+        DeriveIterator::count_items(fns, types)
+    }
+
+    fn derive_type(&self, item: SomeType, this: OuterType) -> Result<TokenStream> {
+        derive_iterable(item, this)
+    }
+
+    fn unsupported<T, S: Spanned>(span: S) -> Result<T> {
+        DeriveIterator::unsupported(span)
+    }
+
+    fn impl_func(item: ImplItemFn) -> Result<SomeFunc> {
+        DeriveIterator::impl_func(item)
+    }
+
+    fn trait_func(item: TraitItemFn) -> Result<SomeFunc> {
+        DeriveIterator::trait_func(item)
+    }
+
+    fn derive_func(item: SomeFunc, this: OuterType) -> Result<TokenStream> {
+        DeriveIterator::derive_func(item, this)
+    }
+}
+
+impl DeriveInterface for DeriveGenerator {
+    fn impl_type(item: ImplItemType) -> Result<SomeType> {
+        type_named_generator(&item.ident)?;
+        impl_assoc_type(item)
+    }
+
+    fn trait_type(item: TraitItemType) -> Result<SomeType> {
+        type_named_generator(&item.ident)?;
+        trait_assoc_type(item)
+    }
+
+    fn count_items(fns: usize, types: usize) -> Result<()> {
+        match (fns, types) {
+            (_, 3) => Ok(()),
+            (_, _) => "expected `type Yield = ...`, `type Return = ...`, and `type Next = ...`"
+                .pipe(Error::custom)
+                .pipe(Err),
+        }
+    }
+
+    fn derive_type(&self, item: SomeType, this: OuterType) -> Result<TokenStream> {
+        self.derive_types(vec![item], this)
+    }
+
+    fn derive_types(&self, items: Vec<SomeType>, this: OuterType) -> Result<TokenStream> {
+        derive_generator(items, this)
+    }
+
+    fn unsupported<T, S: Spanned>(span: S) -> Result<T> {
+        DeriveIterator::unsupported(span)
+    }
+
+    fn impl_func(item: ImplItemFn) -> Result<SomeFunc> {
+        DeriveIterator::impl_func(item)
+    }
+
+    fn trait_func(item: TraitItemFn) -> Result<SomeFunc> {
+        DeriveIterator::trait_func(item)
+    }
+
+    fn derive_func(item: SomeFunc, this: OuterType) -> Result<TokenStream> {
+        DeriveIterator::derive_func(item, this)
+    }
+}
+
+fn derive_iterator(
+    SomeType { ty, .. }: SomeType,
+    OuterType {
+        this,
+        generics,
+        kind,
+    }: OuterType,
+    asyncness: bool,
+    batch: Option<usize>,
+) -> Result<TokenStream> {
+    let mut errors = Error::accumulator();
+
+    let item_type = V8Conv::from_type(ty).and_recover(&mut errors);
+
+    let return_ty = item_type.to_type();
+
+    let read_result = read_result(&item_type);
+
+    let vis = match kind {
+        OuterTypeKind::Impl => quote! { pub },
+        OuterTypeKind::Trait => quote! {},
+    };
+
+    let step_result = quote! { ::ferrosaur::Result<Option<#return_ty>> };
+
+    let render_step = |method: &str, binding: &str, inputs: TokenStream, before: TokenStream| {
+        let binding = format_ident!("{binding}");
+
+        let site = CallSite {
+            binding: &binding,
+            path: Some(method.into()),
+            asyncness,
+        };
+
+        let (args, length) = if inputs.is_empty() {
+            (quote! { [] }, 0)
+        } else {
+            (quote! { [error] }, 1)
+        };
+
+        let fn_value = BindFunction {
+            source: method.into(),
+            this: FunctionThis::Self_,
+            ctor: false,
+            length: FunctionLength::Fixed(length),
+            throws: None,
+        };
+
+        let call = quote! {
+            let this = ToV8::to_v8(&*self, scope)
+                .map_err(::ferrosaur::Error::other)?;
+            let this = v8::Local::new(scope, this);
+            #before
+            #fn_value
+            call(scope, this, #args)?
+        };
+
+        let get_result = if asyncness {
+            quote! {
+                let result = {
+                    let scope = &mut rt.handle_scope();
+                    let result = { #call };
+                    v8::Global::new(scope, result)
+                };
+                let future = rt.resolve(result);
+                let result = rt
+                    .with_event_loop_promise(future, Default::default())
+                    .await?;
+                let scope = &mut rt.handle_scope();
+                let result = v8::Local::new(scope, result);
+            }
+        } else {
+            quote! {
+                let scope = &mut rt.handle_scope();
+                let result = { #call };
+            }
+        };
+
+        let body = site.wrap(
+            &step_result,
+            quote! {
+                #get_result
+                #read_result
+            },
+        );
+
+        let generics = if inputs.is_empty() {
+            quote! {}
+        } else {
+            quote! { <E> }
+        };
+
+        let where_clause = if inputs.is_empty() {
+            quote! {}
+        } else {
+            quote! { where E: for<'a> ToV8<'a> }
+        };
+
+        if asyncness {
+            let allow_async_fn = match kind {
+                OuterTypeKind::Impl => quote! {},
+                OuterTypeKind::Trait => quote! { #[allow(async_fn_in_trait)] },
+            };
+            quote! {
+                #allow_async_fn
+                #vis async fn #binding #generics(
+                    &mut self,
+                    #inputs
+                    rt: &mut JsRuntime,
+                ) -> #step_result
+                #where_clause
+                {
+                    #body
+                }
+            }
+        } else {
+            quote! {
+                #[track_caller]
+                #vis fn #binding #generics(
+                    &mut self,
+                    #inputs
+                    rt: &mut JsRuntime,
+                ) -> #step_result
+                #where_clause
+                {
+                    #body
+                }
+            }
+        }
+    };
+
+    let fn_next = render_step("next", "next", quote! {}, quote! {});
+
+    let fn_return = {
+        let return_key = PropertyKey::from("return");
+        render_step(
+            "return",
+            "return_",
+            quote! {},
+            quote! {
+                let method = {
+                    let object = this
+                        .try_cast::<v8::Object>()
+                        .map_err(|_| ::ferrosaur::Error::not_an_object())?;
+                    let prop = #return_key;
+                    object.get(scope, prop.into())
+                };
+                if method.is_none_or(|method| method.is_null_or_undefined()) {
+                    return Ok(None);
+                }
+            },
+        )
+    };
+
+    let fn_throw = render_step(
+        "throw",
+        "throw",
+        quote! { error: E, },
+        quote! {
+            let error = ToV8::to_v8(error, scope)
+                .map_err(|err| ::ferrosaur::Error::argument(0, "error", err))?;
+        },
+    );
+
+    if asyncness {
+        let capturing = match kind {
+            OuterTypeKind::Impl => quote! { + use<'_iter> },
+            OuterTypeKind::Trait => quote! {},
+        };
+
+        return errors.finish_with(quote! {
+            #fn_next
+            #fn_return
+            #fn_throw
+
+            #vis fn into_stream<'_iter>(
+                self,
+                rt: &'_iter mut JsRuntime,
+            ) -> impl deno_core::futures::Stream<Item = ::ferrosaur::Result<#return_ty>> #capturing
+            {
+                deno_core::futures::stream::unfold((self, rt), |(mut inner, rt)| async move {
+                    let item = inner.next(rt).await.transpose()?;
+                    Some((item, (inner, rt)))
+                })
+            }
+        });
+    }
+
+    let fn_into_iter = {
+        let iter_lifetime = quote! { '_iter };
+        let trait_generic = format_ident!("_Inner");
+        let runtime_generic = format_ident!("_Rt");
+
+        let capturing = match kind {
+            OuterTypeKind::Impl => quote! { + use<#iter_lifetime> },
+            OuterTypeKind::Trait => quote! {},
+        };
+
+        let capturing_shared = match kind {
+            OuterTypeKind::Impl => quote! { + use<> },
+            OuterTypeKind::Trait => quote! {},
+        };
+
+        let inner_type = match kind {
+            OuterTypeKind::Impl => quote! { #this },
+            OuterTypeKind::Trait => quote! { #trait_generic },
+        };
+
+        let generics = match kind {
+            OuterTypeKind::Impl => MergeGenerics {
+                outer: generics,
+                lifetimes: vec![],
+                types: vec![quote! { #runtime_generic }],
+                bounds: vec![quote! { #runtime_generic: ::ferrosaur::BorrowRuntime }],
+            },
+            OuterTypeKind::Trait => {
+                let outer_args = MergeGenerics {
+                    outer: generics,
+                    lifetimes: vec![],
+                    types: vec![],
+                    bounds: vec![],
+                }
+                .arguments();
+                MergeGenerics {
+                    outer: generics,
+                    lifetimes: vec![],
+                    types: vec![quote! { #trait_generic }, quote! { #runtime_generic }],
+                    bounds: vec![
+                        to_v8_bound(type_ident(trait_generic.clone())).to_token_stream(),
+                        quote! { #trait_generic: #this <#outer_args> },
+                        quote! { #runtime_generic: ::ferrosaur::BorrowRuntime },
+                    ],
+                }
+            }
+        };
+
+        let params = generics.params();
+        let bounds = generics.bounds();
+        let arguments = generics.arguments();
+
+        let phantom_fields = generics.phantom_fields();
+        let phantom_init = generics.phantom_init();
+
+        let (buffer_field, buffer_init, next_item) = match batch {
+            None => (
+                quote! {},
+                quote! {},
+                quote! {
+                    if self.done {
+                        return None;
+                    }
+                    let item = match self.rt.borrow_runtime() {
+                        Ok(mut rt) => self.inner.next(&mut rt).transpose(),
+                        Err(error) => Some(Err(::ferrosaur::Error::other(error))),
+                    };
+                    if !matches!(item, Some(Ok(_))) {
+                        self.done = true;
+                    }
+                    item
+                },
+            ),
+            Some(size) => {
+                let into_item = item_type.to_cast_from_v8("value", "scope");
+
+                let pull = CallSite {
+                    binding: &format_ident!("into_iter"),
+                    path: Some("next".into()),
+                    asyncness: false,
+                }
+                .wrap_as(
+                    &inner_type,
+                    quote! { ::ferrosaur::Result<bool> },
+                    quote! {
+                        let scope = &mut rt.handle_scope();
+                        let this = ToV8::to_v8(inner, scope)
+                            .map_err(::ferrosaur::Error::other)?;
+                        let this = v8::Local::new(scope, this);
+                        let ::ferrosaur::Batch { items, done, error } =
+                            ::ferrosaur::next_batch(scope, this, #size)?;
+                        for value in items {
+                            let item = #into_item
+                                .map_err(::ferrosaur::Error::output)?;
+                            buffer.push_back(Ok(item));
+                        }
+                        match error {
+                            Some(error) => Err(error),
+                            None => Ok(done),
+                        }
+                    },
+                );
+
+                (
+                    quote! {
+                        buffer: ::std::collections::VecDeque<::ferrosaur::Result<#return_ty>>,
+                    },
+                    quote! {
+                        buffer: Default::default(),
+                    },
+                    quote! {
+                        if let Some(item) = self.buffer.pop_front() {
+                            return Some(item);
+                        }
+                        if self.done {
+                            return None;
+                        }
+                        let mut rt = match self.rt.borrow_runtime() {
+                            Ok(rt) => rt,
+                            Err(error) => {
+                                self.done = true;
+                                return Some(Err(::ferrosaur::Error::other(error)));
+                            }
+                        };
+                        let rt = &mut *rt;
+                        let inner = &self.inner;
+                        let buffer = &mut self.buffer;
+                        let done: ::ferrosaur::Result<bool> = { #pull };
+                        match done {
+                            Ok(done) => self.done = done,
+                            Err(error) => {
+                                self.buffer.push_back(Err(error));
+                                self.done = true;
+                            }
+                        }
+                        self.buffer.pop_front()
+                    },
+                )
+            }
+        };
+
+        let iter_struct = quote! {
+            struct Iter <#params>
+            where
+                #bounds
+            {
+                rt: #runtime_generic,
+                inner: #inner_type,
+                done: bool,
+                #buffer_field
+                #phantom_fields
+            }
+
+            impl <#params> ::core::iter::Iterator for Iter <#arguments>
+            where
+                #bounds
+            {
+                type Item = ::ferrosaur::Result<#return_ty>;
+
+                fn next(&mut self) -> Option<Self::Item> {
+                    #next_item
+                }
+            }
+
+            impl <#params> ::core::ops::Drop for Iter <#arguments>
+            where
+                #bounds
+            {
+                /// Close the iterator if it was not exhausted, like `for...of` does.
+                fn drop(&mut self) {
+                    if self.done {
+                        return;
+                    }
+                    if let Ok(mut rt) = self.rt.borrow_runtime() {
+                        self.inner.return_(&mut rt).ok();
+                    }
+                }
+            }
+        };
+
+        quote! {
+            #vis fn into_iter<#iter_lifetime>(
+                self,
+                rt: &#iter_lifetime mut JsRuntime,
+            ) -> impl Iterator<Item = ::ferrosaur::Result<#return_ty>> #capturing
+            {
+                #iter_struct
+                Iter { rt, inner: self, done: false, #buffer_init #phantom_init }
+            }
+
+            #vis fn into_shared_iter(
+                self,
+                rt: ::std::rc::Rc<::core::cell::RefCell<JsRuntime>>,
+            ) -> impl Iterator<Item = ::ferrosaur::Result<#return_ty>> #capturing_shared
+            {
+                #iter_struct
+                Iter { rt, inner: self, done: false, #buffer_init #phantom_init }
+            }
+        }
+    };
+
+    errors.finish_with(quote! {
+        #fn_next
+        #fn_return
+        #fn_throw
+        #fn_into_iter
+    })
+}
+
+fn derive_iterable(
+    SomeType { ty, .. }: SomeType,
+    OuterType { generics, kind, .. }: OuterType,
+) -> Result<TokenStream> {
+    let mut errors = Error::accumulator();
+
+    let item_type = V8Conv::from_type(ty).and_recover(&mut errors);
+
+    let return_ty = item_type.to_type();
+
+    let read_result = read_result(&item_type);
+
+    let vis = match kind {
+        OuterTypeKind::Impl => quote! { pub },
+        OuterTypeKind::Trait => quote! {},
+    };
+
+    let capturing = match kind {
+        OuterTypeKind::Impl => {
+            let types = generics.type_params().map(|ty| &ty.ident);
+            quote! { + use<'_iter #(, #types)*> }
+        }
+        OuterTypeKind::Trait => quote! {},
+    };
+
+    let capturing_shared = match kind {
+        OuterTypeKind::Impl => {
+            let types = generics.type_params().map(|ty| &ty.ident);
+            quote! { + use<#(#types),*> }
+        }
+        OuterTypeKind::Trait => quote! {},
+    };
+
+    let bind = |source: PropertyKey| BindFunction {
+        source: source.into(),
+        this: FunctionThis::Self_,
+        ctor: false,
+        length: FunctionLength::Fixed(0),
+        throws: None,
+    };
+
+    let fn_iterator = bind(PropertyKey::Symbol(WellKnown::Iterator));
+    let fn_next = bind("next".into());
+    let fn_return = bind("return".into());
+
+    let return_key = PropertyKey::from("return");
+
+    let iter_result = quote! {
+        ::ferrosaur::Result<impl Iterator<Item = ::ferrosaur::Result<#return_ty>> #capturing>
+    };
+
+    let shared_iter_result = quote! {
+        ::ferrosaur::Result<impl Iterator<Item = ::ferrosaur::Result<#return_ty>> #capturing_shared>
+    };
+
+    let render_body = |binding: &str, this: TokenStream| {
+        let binding = format_ident!("{binding}");
+
+        let get_iterator = CallSite {
+            binding: &binding,
+            path: Some("[Symbol.iterator]".into()),
+            asyncness: false,
+        }
+        .wrap(
+            quote! { ::ferrosaur::Result<v8::Global<v8::Value>> },
+            quote! {
+                let scope = &mut rt.handle_scope();
+                let this = ToV8::to_v8(#this, scope)
+                    .map_err(::ferrosaur::Error::other)?;
+                let this = v8::Local::new(scope, this);
+                #fn_iterator
+                let iterator = call(scope, this, [])?;
+                Ok(v8::Global::new(scope, iterator))
+            },
+        );
+
+        let next_item = CallSite {
+            binding: &binding,
+            path: Some("next".into()),
+            asyncness: false,
+        }
+        .wrap(
+            quote! { ::ferrosaur::Result<Option<#return_ty>> },
+            quote! {
+                let scope = &mut rt.handle_scope();
+                let this = v8::Local::new(scope, iterator);
+                #fn_next
+                let result = call(scope, this, [])?;
+                #read_result
+            },
+        );
+
+        quote! {
+            let mut rt = rt;
+
+            let iterator = {
+                let mut rt = ::ferrosaur::BorrowRuntime::borrow_runtime(&mut rt)
+                    .map_err(::ferrosaur::Error::other)?;
+                let rt = &mut *rt;
+                #get_iterator
+            }?;
+
+            struct Iter<_Rt>
+            where
+                _Rt: ::ferrosaur::BorrowRuntime,
+            {
+                rt: _Rt,
+                iterator: v8::Global<v8::Value>,
+                done: bool,
+            }
+
+            impl<_Rt> ::core::ops::Drop for Iter<_Rt>
+            where
+                _Rt: ::ferrosaur::BorrowRuntime,
+            {
+                /// Close the iterator if it was not exhausted, like `for...of` does.
+                fn drop(&mut self) {
+                    if self.done {
+                        return;
+                    }
+                    let Ok(mut rt) = self.rt.borrow_runtime() else {
+                        return;
+                    };
+                    let rt = &mut *rt;
+                    let iterator = &self.iterator;
+                    (move || -> ::ferrosaur::Result<()> {
+                        let scope = &mut rt.handle_scope();
+                        let this = v8::Local::new(scope, iterator);
+                        let method = {
+                            let object = this
+                                .try_cast::<v8::Object>()
+                                .map_err(|_| ::ferrosaur::Error::not_an_object())?;
+                            let prop = #return_key;
+                            object.get(scope, prop.into())
+                        };
+                        if method.is_none_or(|method| method.is_null_or_undefined()) {
+                            return Ok(());
+                        }
+                        #fn_return
+                        call(scope, this, [])?;
+                        Ok(())
+                    })()
+                    .ok();
+                }
+            }
+
+            let mut iter = Iter {
+                rt,
+                iterator,
+                done: false,
+            };
+
+            Ok(::core::iter::from_fn(move || {
+                if iter.done {
+                    return None;
+                }
+                let item = match ::ferrosaur::BorrowRuntime::borrow_runtime(&mut iter.rt) {
+                    Ok(mut rt) => {
+                        let rt = &mut *rt;
+                        let iterator = &iter.iterator;
+                        #next_item
+                    }
+                    Err(error) => Err(::ferrosaur::Error::other(error)),
+                };
+                if !matches!(item, Ok(Some(_))) {
+                    iter.done = true;
+                }
+                item.transpose()
+            }))
+        }
+    };
+
+    let iter_body = render_body("iter", quote! { self });
+    let into_iter_body = render_body("into_iter", quote! { &self });
+    let into_shared_iter_body = render_body("into_shared_iter", quote! { &self });
+
+    let sized = match kind {
+        OuterTypeKind::Impl => quote! {},
+        OuterTypeKind::Trait => quote! { where Self: Sized },
+    };
+
+    errors.finish_with(quote! {
+        #[track_caller]
+        #vis fn iter<'_iter>(
+            &self,
+            rt: &'_iter mut JsRuntime,
+        ) -> #iter_result
+        {
+            #iter_body
+        }
+
+        #[track_caller]
+        #vis fn into_iter<'_iter>(
+            self,
+            rt: &'_iter mut JsRuntime,
+        ) -> #iter_result
+        #sized
+        {
+            #into_iter_body
+        }
+
+        #[track_caller]
+        #vis fn into_shared_iter(
+            self,
+            rt: ::std::rc::Rc<::core::cell::RefCell<JsRuntime>>,
+        ) -> #shared_iter_result
+        #sized
+        {
+            #into_shared_iter_body
+        }
+    })
+}
+
+fn derive_generator(
+    items: Vec<SomeType>,
+    OuterType { this, kind, .. }: OuterType,
+) -> Result<TokenStream> {
+    let mut errors = Error::accumulator();
+
+    let mut yield_type = None;
+    let mut return_type = None;
+    let mut next_type = None;
+
+    for SomeType { ident, ty } in items {
+        let slot = if ident == "Yield" {
+            &mut yield_type
+        } else if ident == "Return" {
+            &mut return_type
+        } else {
+            &mut next_type
+        };
+        if slot.is_some() {
+            format!("duplicate `type {ident}`")
+                .pipe(Error::custom)
+                .with_span(&ident)
+                .pipe(|e| errors.push(e));
+        } else {
+            *slot = Some(V8Conv::from_type(ty).and_recover(&mut errors));
+        }
+    }
+
+    let (types, errors) = match (yield_type, return_type, next_type) {
+        (Some(y), Some(r), Some(n)) => Ok((y, r, n)),
+        _ => "expected `type Yield = ...`, `type Return = ...`, and `type Next = ...`"
+            .pipe(Error::custom)
+            .with_span(this)
+            .pipe(Err),
+    }
+    .or_fatal(errors)?;
+
+    let (yield_type, return_type, next_type) = types;
+
+    let yield_ty = yield_type.to_type();
+    let return_ty = return_type.to_type();
+    let next_ty = next_type.to_type();
+
+    let into_yield = yield_type.to_cast_from_v8("value", "scope");
+    let into_return = return_type.to_cast_from_v8("value", "scope");
+    let from_next = next_type.to_cast_into_v8("value", "scope");
+
+    let read_done_value = read_done_value();
+
+    let vis = match kind {
+        OuterTypeKind::Impl => quote! { pub },
+        OuterTypeKind::Trait => quote! {},
+    };
+
+    let fn_next = BindFunction {
+        source: "next".into(),
+        this: FunctionThis::Self_,
+        ctor: false,
+        length: FunctionLength::Fixed(1),
+        throws: None,
+    };
+
+    let resume_result = quote! {
+        ::ferrosaur::Result<::ferrosaur::GeneratorState<#yield_ty, #return_ty>>
+    };
+
+    let resume_body = CallSite {
+        binding: &format_ident!("resume"),
+        path: Some("next".into()),
+        asyncness: false,
+    }
+    .wrap(
+        &resume_result,
+        quote! {
+            let scope = &mut rt.handle_scope();
+            let value = #from_next
+                .map_err(|err| ::ferrosaur::Error::argument(0, "value", err))?;
+            let this = ToV8::to_v8(&*self, scope)
+                .map_err(::ferrosaur::Error::other)?;
+            let this = v8::Local::new(scope, this);
+            #fn_next
+            let result = call(scope, this, [value])?;
+            #read_done_value
+            if done.is_true() {
+                let value = #into_return
+                    .map_err(::ferrosaur::Error::output)?;
+                Ok(::ferrosaur::GeneratorState::Complete(value))
+            } else {
+                let value = #into_yield
+                    .map_err(::ferrosaur::Error::output)?;
+                Ok(::ferrosaur::GeneratorState::Yielded(value))
+            }
+        },
+    );
+
+    errors.finish_with(quote! {
+        #[track_caller]
+        #vis fn resume(
+            &mut self,
+            value: #next_ty,
+            rt: &mut JsRuntime,
+        ) -> #resume_result
+        {
+            #resume_body
+        }
+    })
+}
+
+/// Interpret `result` returned by `next()`, `return()`, or `throw()` as an item.
+fn read_result(item_type: &V8Conv) -> TokenStream {
+    let read_done_value = read_done_value();
+
+    let into_item = item_type.to_cast_from_v8("value", "scope");
+
+    quote! {
+        #read_done_value
+        if done.is_true() && value.is_undefined() {
+            Ok(None)
+        } else {
+            let item = #into_item
+                .map_err(::ferrosaur::Error::output)?;
+            Ok(Some(item))
+        }
+    }
+}
+
+/// Read `done` and `value` from an iterator result object.
+fn read_done_value() -> TokenStream {
+    let value_key = PropertyKey::from("value");
+    let value_getter = V8Conv::default().to_getter(&Default::default());
+
+    let done_key = PropertyKey::from("done");
+    let done_getter = V8Conv::default().to_getter(&Default::default());
+
+    quote! {
+        let done = {
+            #done_getter
+            let prop = #done_key;
+            getter(scope, result, prop)?
+        };
+        let value = {
+            #value_getter
+            let prop = #value_key;
+            getter(scope, result, prop)?
+        };
+        let done = v8::Local::new(scope, done);
+        let value = v8::Local::new(scope, value);
+    }
+}
+
+fn impl_assoc_type(item: ImplItemType) -> Result<SomeType> {
+    let ImplItemType {
+        attrs,
+        defaultness,
+        vis,
+        ident,
+        generics,
+        ty,
+        ..
+    } = item;
+
+    let mut errors = Error::accumulator();
+
+    errors.handle(no_attributes(attrs));
+    errors.handle(no_defaultness(defaultness));
+    errors.handle(no_generics(&generics));
+    errors.handle(no_where_clause(&generics));
+    errors.handle(no_visibility(vis));
+
+    errors.finish_with(SomeType { ident, ty })
+}
+
+fn trait_assoc_type(item: TraitItemType) -> Result<SomeType> {
+    let TraitItemType {
+        attrs,
+        ident,
+        generics,
+        bounds,
+        default,
+        ..
+    } = item;
+
+    let mut errors = Error::accumulator();
+
+    errors.handle(no_attributes(attrs));
+    errors.handle(no_generics(&generics));
+    errors.handle(no_where_clause(&generics));
+    errors.handle(no_bounds(bounds));
+
+    let (ty, errors) = match default {
+        None => format!("a concrete type is required: `type {ident} = ...`")
+            .pipe(Error::custom)
+            .with_span(&ident)
+            .pipe(Err),
+        Some((_, ty)) => Ok(ty),
+    }
+    .or_fatal(errors)?;
+
+    errors.finish_with(SomeType { ident, ty })
+}
+
+fn type_named_generator(ident: &Ident) -> Result<()> {
+    if ident != "Yield" && ident != "Return" && ident != "Next" {
+        "unexpected type name, expected `type Yield`, `type Return`, or `type Next`"
+            .pipe(Error::custom)
+            .with_span(&ident)
+            .pipe(Err)
+    } else {
+        Ok(())
+    }
+}
+
+fn type_named_item(ident: &Ident) -> Result<()> {
+    if ident != "Item" {
+        "unexpected type name, expected `type Item`"
+            .pipe(Error::custom)
+            .with_span(&ident)
+            .pipe(Err)
+    } else {
+        Ok(())
+    }
+}
+
+fn no_attributes(attrs: Vec<Attribute>) -> Result<()> {
+    if !attrs.is_empty() {
+        Error::custom("macro ignores attributes in this location")
+            .with_span(&quote! { #(#attrs)* })
+            .pipe(Err)
+    } else {
+        Ok(())
+    }
+}
+
+fn no_defaultness(defaultness: Option<Token![default]>) -> Result<()> {
+    if defaultness.is_some() {
+        Error::custom("macro ignores `default` in this location")
+            .with_span(&defaultness)
+            .pipe(Err)
+    } else {
+        Ok(())
+    }
+}
+
+fn no_generics(generics: &Generics) -> Result<()> {
+    if !generics.params.is_empty() {
+        Error::custom("macro ignores type params in this location")
+            .with_span(&generics.params)
+            .pipe(Err)
+    } else {
+        Ok(())
+    }
+}
+
+fn no_where_clause(generics: &Generics) -> Result<()> {
+    if generics.where_clause.is_some() {
+        Error::custom("macro ignores where clause in this location")
+            .with_span(&generics.where_clause)
+            .pipe(Err)
+    } else {
+        Ok(())
+    }
+}
+
+fn no_visibility(vis: Visibility) -> Result<()> {
+    if matches!(vis, Visibility::Public(_) | Visibility::Restricted(_)) {
+        Error::custom("macro ignores visibility qualifiers in this location")
+            .with_span(&vis)
+            .pipe(Err)
+    } else {
+        Ok(())
+    }
+}
+
+fn no_bounds(bounds: Punctuated<TypeParamBound, Token![+]>) -> Result<()> {
+    if bounds.is_empty() {
+        Ok(())
+    } else {
+        Error::custom("macro ignores trait bounds in this location")
+            .with_span(&bounds)
+            .pipe(Err)
+    }
+}
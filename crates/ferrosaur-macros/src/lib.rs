@@ -0,0 +1,513 @@
+#![doc = concat!("**Usage documentation** at <", env!("CARGO_PKG_HOMEPAGE"), ">.")]
+
+use darling::{
+    ast::NestedMeta,
+    util::{path_to_string, Flag},
+    Error, FromMeta, Result,
+};
+use proc_macro2::TokenStream;
+use syn::{parse_macro_input, Lit, LitStr, Meta};
+
+mod callable;
+mod class;
+mod error;
+mod fast_string;
+mod global_this;
+mod host_module;
+mod interface;
+mod iterator;
+mod module;
+mod util;
+mod value;
+
+use crate::util::{
+    flag::{FlagEnum, FlagError, FlagLike, FlagName},
+    positional::Positional,
+    property::WellKnown,
+    string::StringLike,
+    ty::TypeLike,
+    unary::Unary,
+    v8::V8InnerType,
+    FatalErrors, TokenStreamResult,
+};
+
+#[doc = concat!("**Usage documentation** at <", env!("CARGO_PKG_HOMEPAGE"), ">.")]
+#[proc_macro_attribute]
+pub fn js(attr: proc_macro::TokenStream, item: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    js_item(attr.into(), item.into()).or_error().into()
+}
+
+fn js_item(args: TokenStream, item: TokenStream) -> Result<TokenStream> {
+    let errors = Error::accumulator();
+
+    let (js, errors) = FlagLike::<JsItem>::parse_macro_attribute(args).or_fatal(errors)?;
+
+    let (js, errors) = match js.0 {
+        JsItem::Value(value) => {
+            let FlagLike(value) = *value;
+            value::value(value, item).error_at::<JsItem, Value>()
+        }
+
+        JsItem::Module(FlagLike(module)) => {
+            module::module(module, item).error_at::<JsItem, Module>()
+        }
+        JsItem::GlobalThis(FlagLike(global_this)) => {
+            global_this::global_this(global_this, item).error_at::<JsItem, GlobalThis>()
+        }
+        JsItem::Interface(FlagLike(interface)) => {
+            interface::interface(interface, item).error_at::<JsItem, Interface>()
+        }
+        JsItem::Callable(FlagLike(callable)) => {
+            callable::callable(callable, item).error_at::<JsItem, Callable>()
+        }
+        JsItem::Iterator(FlagLike(iterator)) => {
+            iterator::iterator(iterator, item).error_at::<JsItem, Iterator_>()
+        }
+        JsItem::AsyncIterator(FlagLike(iterator)) => {
+            iterator::async_iterator(iterator, item).error_at::<JsItem, AsyncIterator>()
+        }
+        JsItem::Iterable(FlagLike(iterable)) => {
+            iterator::iterable(iterable, item).error_at::<JsItem, Iterable>()
+        }
+        JsItem::Generator(FlagLike(generator)) => {
+            iterator::generator(generator, item).error_at::<JsItem, Generator>()
+        }
+        JsItem::Error(FlagLike(error)) => error::error(error, item).error_at::<JsItem, Error_>(),
+        JsItem::Class(FlagLike(class)) => class::class(class, item).error_at::<JsItem, Class>(),
+        JsItem::Export(FlagLike(export)) => {
+            class::export(export, item).error_at::<JsItem, Export>()
+        }
+        JsItem::HostModule(FlagLike(host)) => {
+            host_module::host_module(host, item).error_at::<JsItem, HostModule>()
+        }
+    }
+    .or_fatal(errors)?;
+
+    errors.finish_with(js)
+}
+
+#[derive(Debug, Clone, FromMeta)]
+#[darling(rename_all = "snake_case")]
+enum JsItem {
+    Module(FlagLike<Module>),
+    GlobalThis(FlagLike<GlobalThis>),
+    Value(Box<FlagLike<Value>>),
+    Interface(FlagLike<Interface>),
+    Callable(FlagLike<Callable>),
+    Iterator(FlagLike<Iterator_>),
+    AsyncIterator(FlagLike<AsyncIterator>),
+    Iterable(FlagLike<Iterable>),
+    Generator(FlagLike<Generator>),
+    Error(FlagLike<Error_>),
+    Class(FlagLike<Class>),
+    Export(FlagLike<Export>),
+    HostModule(FlagLike<HostModule>),
+}
+
+#[derive(Debug, Clone, FromMeta)]
+#[darling(rename_all = "snake_case")]
+enum JsProp {
+    Prop(FlagLike<Property>),
+    Func(FlagLike<Function>),
+    New(FlagLike<Constructor>),
+    GetIndex(FlagLike<Getter>),
+    SetIndex(FlagLike<Setter>),
+}
+
+#[derive(Debug, Clone, FromMeta)]
+#[darling(rename_all = "snake_case")]
+enum JsMember {
+    Constructor(FlagLike<ClassConstructor>),
+    Method(FlagLike<Method>),
+}
+
+#[derive(Debug, Clone, FromMeta)]
+struct Module(Positional<String, ModuleOptions>);
+
+#[derive(Debug, Clone, FromMeta)]
+struct ModuleOptions {
+    #[darling(default)]
+    url: ImportMetaUrl,
+    fast: Option<FastString>,
+}
+
+#[derive(Debug, Default, Clone)]
+enum ImportMetaUrl {
+    #[default]
+    Preserve,
+    Cwd,
+    Url(String),
+}
+
+#[derive(Debug, Clone, Copy)]
+enum FastString {
+    Fast,
+    FastUnsafeDebug,
+}
+
+#[derive(Debug, Default, Clone, FromMeta)]
+struct GlobalThis;
+
+#[derive(Debug, Default, Clone, FromMeta)]
+struct Value {
+    #[darling(default)]
+    of_type: Unary<V8InnerType>,
+}
+
+#[derive(Debug, Default, Clone, FromMeta)]
+struct Interface {
+    host: Option<Unary<TypeLike>>,
+}
+
+#[derive(Debug, Default, Clone, FromMeta)]
+struct Callable;
+
+#[derive(Debug, Default, Clone, FromMeta)]
+struct Iterator_ {
+    batch: Option<usize>,
+}
+
+#[derive(Debug, Default, Clone, FromMeta)]
+struct AsyncIterator;
+
+#[derive(Debug, Default, Clone, FromMeta)]
+struct Iterable;
+
+#[derive(Debug, Default, Clone, FromMeta)]
+struct Generator;
+
+#[derive(Debug, Default, Clone, FromMeta)]
+struct Error_;
+
+#[derive(Debug, Default, Clone, FromMeta)]
+struct Class {
+    name: Option<Unary<PropKeyString>>,
+}
+
+#[derive(Debug, Default, Clone, FromMeta)]
+struct Export {
+    name: Option<Unary<PropKeyString>>,
+}
+
+#[derive(Debug, Clone, FromMeta)]
+struct HostModule(Unary<String>);
+
+#[derive(Debug, Default, Clone, FromMeta)]
+struct ClassConstructor;
+
+#[derive(Debug, Default, Clone, FromMeta)]
+struct Method {
+    name: Option<Unary<PropKeyString>>,
+}
+
+type PropKeyString = StringLike<String>;
+
+type PropKeySymbol = StringLike<WellKnown>;
+
+#[derive(Debug, Default, Clone, FromMeta)]
+struct Property {
+    name: Option<Unary<PropKeyString>>,
+    #[darling(rename = "Symbol")]
+    symbol: Option<Unary<PropKeySymbol>>,
+    with_setter: Flag,
+}
+
+#[derive(Debug, Default, Clone, FromMeta)]
+struct Function {
+    name: Option<Unary<PropKeyString>>,
+    #[darling(rename = "Symbol")]
+    symbol: Option<Unary<PropKeySymbol>>,
+    throws: Option<Unary<TypeLike>>,
+    event_loop: Option<EventLoopOptions>,
+    timeout_ms: Option<u64>,
+}
+
+#[derive(Debug, Default, Clone, FromMeta)]
+struct EventLoopOptions {
+    wait_for_inspector: Option<bool>,
+    pump_v8_message_loop: Option<bool>,
+    none: Flag,
+}
+
+#[derive(Debug, Default, Clone, FromMeta)]
+struct Constructor {
+    class: Option<Unary<PropKeyString>>,
+}
+
+#[derive(Debug, Default, Clone, FromMeta)]
+struct Getter;
+
+#[derive(Debug, Default, Clone, FromMeta)]
+struct Setter;
+
+impl FromMeta for ImportMetaUrl {
+    fn from_list(items: &[NestedMeta]) -> Result<Self> {
+        match items.len() {
+            0 => Err(Error::too_few_items(1)),
+            1 => match &items[0] {
+                NestedMeta::Meta(meta) => match path_to_string(meta.path()).as_str() {
+                    "preserve" => Ok(Self::Preserve),
+                    "cwd" => Ok(Self::Cwd),
+                    other => Err(Error::unknown_field_with_alts(
+                        other,
+                        &["preserve", "cwd", "a string literal"],
+                    )),
+                },
+                NestedMeta::Lit(Lit::Str(lit)) => Ok(Self::Url(lit.value())),
+                NestedMeta::Lit(lit) => Err(Error::unexpected_lit_type(lit)),
+            },
+            _ => Err(Error::too_many_items(1)),
+        }
+    }
+}
+
+impl FromMeta for FastString {
+    fn from_list(items: &[NestedMeta]) -> Result<Self> {
+        match items.len() {
+            0 => Self::from_word(),
+            1 => {
+                let path = match &items[0] {
+                    NestedMeta::Meta(Meta::Path(path)) => path_to_string(path),
+                    NestedMeta::Lit(Lit::Str(lit)) => lit.value(),
+                    NestedMeta::Meta(Meta::List(_)) => {
+                        return Err(Error::unsupported_format("list"))
+                    }
+                    NestedMeta::Meta(Meta::NameValue(_)) => {
+                        return Err(Error::unsupported_format("name value"))
+                    }
+                    NestedMeta::Lit(lit) => return Err(Error::unexpected_lit_type(lit)),
+                };
+                match &*path {
+                    "unsafe_debug" => Ok(Self::FastUnsafeDebug),
+                    path => Err(Error::unknown_field_with_alts(path, &["unsafe_debug"])),
+                }
+            }
+            _ => Err(Error::too_many_items(1)),
+        }
+    }
+
+    fn from_word() -> Result<Self> {
+        Ok(Self::Fast)
+    }
+}
+
+impl FlagName for JsItem {
+    const PREFIX: &'static str = "js";
+
+    fn unit() -> Result<Self> {
+        JsItem::from_word()
+    }
+}
+
+impl FlagEnum for JsItem {
+    const PREFIXES: &'static [&'static str] = &[
+        Module::PREFIX,
+        GlobalThis::PREFIX,
+        Value::PREFIX,
+        Interface::PREFIX,
+        Iterator_::PREFIX,
+        AsyncIterator::PREFIX,
+        Iterable::PREFIX,
+        Generator::PREFIX,
+        Error_::PREFIX,
+        Class::PREFIX,
+        Export::PREFIX,
+        HostModule::PREFIX,
+    ];
+}
+
+impl FlagName for JsProp {
+    const PREFIX: &'static str = "js";
+
+    fn unit() -> Result<Self> {
+        JsProp::from_word()
+    }
+}
+
+impl FlagEnum for JsProp {
+    const PREFIXES: &'static [&'static str] = &[
+        Property::PREFIX,
+        Function::PREFIX,
+        Constructor::PREFIX,
+        Getter::PREFIX,
+        Setter::PREFIX,
+    ];
+}
+
+impl FlagName for JsMember {
+    const PREFIX: &'static str = "js";
+
+    fn unit() -> Result<Self> {
+        JsMember::from_word()
+    }
+}
+
+impl FlagEnum for JsMember {
+    const PREFIXES: &'static [&'static str] = &[ClassConstructor::PREFIX, Method::PREFIX];
+}
+
+impl FlagName for Module {
+    const PREFIX: &'static str = "module";
+
+    fn unit() -> Result<Self> {
+        Module::from_word()
+    }
+}
+
+impl FlagName for GlobalThis {
+    const PREFIX: &'static str = "global_this";
+
+    fn unit() -> Result<Self> {
+        Ok(Self)
+    }
+}
+
+impl FlagName for Value {
+    const PREFIX: &'static str = "value";
+
+    fn unit() -> Result<Self> {
+        Ok(Default::default())
+    }
+}
+
+impl FlagName for Interface {
+    const PREFIX: &'static str = "interface";
+
+    fn unit() -> Result<Self> {
+        Ok(Default::default())
+    }
+}
+
+impl FlagName for Callable {
+    const PREFIX: &'static str = "callable";
+
+    fn unit() -> Result<Self> {
+        Ok(Self)
+    }
+}
+
+impl FlagName for Iterator_ {
+    const PREFIX: &'static str = "iterator";
+
+    fn unit() -> Result<Self> {
+        Ok(Default::default())
+    }
+}
+
+impl FlagName for AsyncIterator {
+    const PREFIX: &'static str = "async_iterator";
+
+    fn unit() -> Result<Self> {
+        Ok(Self)
+    }
+}
+
+impl FlagName for Iterable {
+    const PREFIX: &'static str = "iterable";
+
+    fn unit() -> Result<Self> {
+        Ok(Self)
+    }
+}
+
+impl FlagName for Generator {
+    const PREFIX: &'static str = "generator";
+
+    fn unit() -> Result<Self> {
+        Ok(Self)
+    }
+}
+
+impl FlagName for Error_ {
+    const PREFIX: &'static str = "error";
+
+    fn unit() -> Result<Self> {
+        Ok(Self)
+    }
+}
+
+impl FlagName for Class {
+    const PREFIX: &'static str = "class";
+
+    fn unit() -> Result<Self> {
+        Ok(Default::default())
+    }
+}
+
+impl FlagName for Export {
+    const PREFIX: &'static str = "export";
+
+    fn unit() -> Result<Self> {
+        Ok(Default::default())
+    }
+}
+
+impl FlagName for HostModule {
+    const PREFIX: &'static str = "host_module";
+
+    fn unit() -> Result<Self> {
+        HostModule::from_word()
+    }
+}
+
+impl FlagName for ClassConstructor {
+    const PREFIX: &'static str = "constructor";
+
+    fn unit() -> Result<Self> {
+        Ok(Self)
+    }
+}
+
+impl FlagName for Method {
+    const PREFIX: &'static str = "method";
+
+    fn unit() -> Result<Self> {
+        Ok(Default::default())
+    }
+}
+
+impl FlagName for Property {
+    const PREFIX: &'static str = "prop";
+
+    fn unit() -> Result<Self> {
+        Ok(Default::default())
+    }
+}
+
+impl FlagName for Function {
+    const PREFIX: &'static str = "func";
+
+    fn unit() -> Result<Self> {
+        Ok(Default::default())
+    }
+}
+
+impl FlagName for Constructor {
+    const PREFIX: &'static str = "new";
+
+    fn unit() -> Result<Self> {
+        Ok(Default::default())
+    }
+}
+
+impl FlagName for Getter {
+    const PREFIX: &'static str = "get_index";
+
+    fn unit() -> Result<Self> {
+        Ok(Self)
+    }
+}
+
+impl FlagName for Setter {
+    const PREFIX: &'static str = "set_index";
+
+    fn unit() -> Result<Self> {
+        Ok(Self)
+    }
+}
+
+#[proc_macro]
+pub fn unsafe_include_fast_string(args: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let path = parse_macro_input!(args as LitStr);
+    fast_string::unsafe_include_fast_string(path).into()
+}
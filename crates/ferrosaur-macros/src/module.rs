@@ -1,6 +1,6 @@
 use darling::{Error, FromDeriveInput, Result};
 use proc_macro2::TokenStream;
-use quote::{format_ident, quote};
+use quote::quote;
 use syn::{
     parse::{Parse, Parser},
     Attribute, DeriveInput, Ident, Visibility,
@@ -65,10 +65,9 @@ pub fn module(module: Module, item: TokenStream) -> Result<TokenStream> {
 
     let const_module_src = match fast {
         Some(FastString::FastUnsafeDebug) => {
-            let this_crate = format_ident!("{}", env!("CARGO_CRATE_NAME"));
             quote! {
                 pub const MODULE_SRC: FastStaticString =
-                    ::#this_crate::unsafe_include_fast_string!(#import);
+                    ::ferrosaur::unsafe_include_fast_string!(#import);
             }
         }
         Some(FastString::Fast) => {
@@ -15,7 +15,9 @@ pub mod function;
 pub mod interface;
 pub mod positional;
 pub mod property;
+pub mod site;
 pub mod string;
+pub mod ty;
 pub mod unary;
 pub mod v8;
 
@@ -255,9 +257,9 @@ pub fn unwrap_v8_local(name: &str) -> TokenStream {
     quote! {{
         let Some(#name) = #name else {
             return if let Some(exception) = scope.exception() {
-                Err(JsError::from_v8_exception(scope, exception))?
+                Err(JsError::from_v8_exception(scope, exception).into())
             } else {
-                Err(anyhow!(#err))
+                Err(::ferrosaur::Error::other(anyhow!(#err)).into())
             };
         };
         #name
@@ -0,0 +1,902 @@
+use darling::Error;
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote, ToTokens};
+use syn::{
+    parse_quote, spanned::Spanned, token::Paren, Expr, ExprPath, FnArg, GenericArgument, Generics,
+    Ident, Pat, PatIdent, PatRange, PatType, Path, PathArguments, RangeLimits, Receiver,
+    ReturnType, Signature, Token, Type, TypePath, TypeTuple,
+};
+use tap::{Pipe, Tap};
+
+use super::{
+    property::PropertyKey,
+    site::CallSite,
+    unwrap_v8_local,
+    v8::{V8Conv, Writeback},
+    Caveat, MergeErrors, RecoverableErrors,
+};
+
+#[derive(Debug, Clone)]
+pub struct CallFunction {
+    pub intent: FunctionIntent,
+    pub source: FunctionSource,
+    pub this: FunctionThis,
+    pub inputs: Vec<FunctionInput>,
+    pub output: Option<V8Conv>,
+    pub throws: Option<Type>,
+    pub event_loop: EventLoopPolicy,
+    pub timeout: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum FunctionIntent {
+    Called,
+    Constructed,
+    Awaited(Token![async]),
+    Deferred,
+}
+
+/// How an awaited promise is driven to completion.
+#[derive(Debug, Clone)]
+pub enum EventLoopPolicy {
+    /// Poll the event loop with [`PollEventLoopOptions`], using defaults for unset fields.
+    ///
+    /// [`PollEventLoopOptions`]: deno_core::PollEventLoopOptions
+    Poll {
+        wait_for_inspector: Option<bool>,
+        pump_v8_message_loop: Option<bool>,
+    },
+    /// Only run microtasks, without polling the event loop.
+    None,
+}
+
+#[derive(Debug, Clone)]
+pub struct FunctionInput {
+    pub ident: Ident,
+    pub ty: V8Conv,
+    pub spread: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct BindFunction {
+    pub source: FunctionSource,
+    pub this: FunctionThis,
+    pub ctor: bool,
+    pub length: FunctionLength,
+    pub throws: Option<Type>,
+}
+
+#[derive(Debug, Clone)]
+pub enum FunctionSource {
+    Prop(PropertyKey),
+    This,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum FunctionLength {
+    Fixed(usize),
+    Variadic,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum FunctionThis {
+    Self_,
+    Undefined { this: Span, undefined: Span },
+    Unbound,
+}
+
+impl CallFunction {
+    pub fn render(
+        &self,
+        fn_self: Option<&Receiver>,
+        fn_name: &Ident,
+        fn_generics: &Generics,
+    ) -> TokenStream {
+        let Self { intent, .. } = self;
+
+        let asyncness = intent;
+
+        let writebacks = self
+            .inputs
+            .iter()
+            .filter(|FunctionInput { spread, .. }| !spread)
+            .filter_map(|FunctionInput { ident, ty, .. }| {
+                ty.to_writeback(ident.to_string(), "__scope")
+                    .map(|writeback| (ident, writeback))
+            })
+            .collect::<Vec<_>>();
+
+        let cast_input = |index: usize, ident: &Ident, ty: &V8Conv| {
+            let name = ident.to_string();
+            match writebacks.iter().find(|(input, _)| *input == ident) {
+                Some((_, Writeback { view, .. })) => quote! { #view.into() },
+                None => {
+                    let var = ty.to_cast_into_v8(&name, "__scope");
+                    let err = argument_error(index, &name);
+                    quote! { #var.map_err(#err)? }
+                }
+            }
+        };
+
+        let views = writebacks
+            .iter()
+            .map(
+                |(
+                    ident,
+                    Writeback {
+                        view, ty, create, ..
+                    },
+                )| {
+                    let index = self
+                        .inputs
+                        .iter()
+                        .position(|input| input.ident == **ident)
+                        .unwrap_or_default();
+                    let err = argument_error(index, &ident.to_string());
+                    quote! {
+                        let #view: v8::Local<#ty> = #create.map_err(#err)?;
+                    }
+                },
+            )
+            .collect::<Vec<_>>();
+
+        let (casts, length) = {
+            let variadic = self
+                .inputs
+                .iter()
+                .any(|FunctionInput { spread, .. }| *spread);
+
+            let length = if variadic {
+                FunctionLength::Variadic
+            } else {
+                FunctionLength::Fixed(self.inputs.len())
+            };
+
+            let casts = if self.inputs.is_empty() {
+                if variadic {
+                    quote! { vec![] }
+                } else {
+                    quote! { [] }
+                }
+            } else if variadic {
+                let casts = self.inputs.iter().enumerate().map(
+                    |(index, FunctionInput { ident, ty, spread })| {
+                        if *spread {
+                            let var = ty.to_cast_into_v8("arg", "__scope");
+                            let err = argument_error(index, &ident.to_string());
+                            quote! {
+                                #[allow(for_loops_over_fallibles)]
+                                for arg in #ident {
+                                    let arg: v8::Local<v8::Value> = #var.map_err(#err)?;
+                                    __args.push(arg);
+                                }
+                            }
+                        } else {
+                            let var = cast_input(index, ident, ty);
+                            quote! {
+                                let #ident: v8::Local<v8::Value> = #var;
+                                __args.push(#ident);
+                            }
+                        }
+                    },
+                );
+
+                quote! {{
+                    let mut __args = Vec::new();
+                    #(#casts)*
+                    __args
+                }}
+            } else {
+                let casts = self.inputs.iter().enumerate().map(|(index, input)| {
+                    let FunctionInput { ident, ty, .. } = input;
+                    let var = cast_input(index, ident, ty);
+                    quote! {
+                        let #ident: v8::Local<v8::Value> = #var;
+                    }
+                });
+
+                let names = self.inputs.iter().map(|FunctionInput { ident, .. }| ident);
+
+                quote! {{
+                    #(#casts)*
+                    [#(#names),*]
+                }}
+            };
+
+            (casts, length)
+        };
+
+        let fn_call = BindFunction {
+            source: self.source.clone(),
+            this: self.this,
+            ctor: matches!(self.intent, FunctionIntent::Constructed),
+            length,
+            throws: self.throws.clone(),
+        };
+
+        let invoke = quote! {
+            #fn_call
+            let __scope = &mut _rt.handle_scope();
+            #(#views)*
+            let args = #casts;
+            let scope = __scope;
+            let object = ToV8::to_v8(self, scope)
+                .map_err(::ferrosaur::Error::other)?;
+            let object = v8::Local::new(scope, object);
+            let output = call(scope, object, args)?;
+        };
+
+        let into_output = match &self.output {
+            None => {
+                quote! {
+                    let _ = output;
+                    Ok(())
+                }
+            }
+            Some(ty) => {
+                let from_output = ty.to_cast_from_v8("output", "scope");
+                quote! {
+                    let output = #from_output
+                        .map_err(::ferrosaur::Error::output)?;
+                    Ok(output)
+                }
+            }
+        };
+
+        let views = writebacks
+            .iter()
+            .map(|(_, Writeback { view, .. })| view)
+            .collect::<Vec<_>>();
+
+        let updates = writebacks
+            .iter()
+            .map(|(_, Writeback { update, .. })| update)
+            .collect::<Vec<_>>();
+
+        let aborts = self
+            .inputs
+            .iter()
+            .filter(|input| matches!(input.ty, V8Conv::Abort { .. }) && !input.spread)
+            .map(|FunctionInput { ident, .. }| ident)
+            .collect::<Vec<_>>();
+
+        let abort_guards = aborts
+            .iter()
+            .map(|ident| format_ident!("__abort_{ident}"))
+            .collect::<Vec<_>>();
+
+        // each call listens on a child token, so that the token does not keep the
+        // AbortController of every past call alive
+        let abort_setup = quote! {
+            #(let #aborts = ::ferrosaur::CancellationToken::child_token(&#aborts);)*
+        };
+
+        let body = match self.intent {
+            FunctionIntent::Called | FunctionIntent::Constructed => quote! {
+                #abort_setup
+                #invoke
+                #(#updates)*
+                #into_output
+            },
+            FunctionIntent::Deferred => {
+                let convert = match &self.output {
+                    None => quote! { |_, _| Ok(()) },
+                    Some(ty) => {
+                        let from_output = ty.to_cast_from_v8("output", "scope");
+                        quote! {
+                            |scope, output| {
+                                let output = #from_output?;
+                                Ok(output)
+                            }
+                        }
+                    }
+                };
+                let output_ty = self.output_type();
+                quote! {
+                    #abort_setup
+                    #invoke
+                    #(#updates)*
+                    let convert: for<'s> fn(
+                        &mut v8::HandleScope<'s>,
+                        v8::Local<'s, v8::Value>,
+                    ) -> Result<#output_ty> = #convert;
+                    let promise = ::ferrosaur::JsPromise::new(scope, output, convert);
+                    #(let promise = promise.with_abort(#aborts);)*
+                    Ok(promise)
+                }
+            }
+            FunctionIntent::Awaited(_) => {
+                let resolve = match &self.event_loop {
+                    EventLoopPolicy::Poll { .. } => {
+                        let options = self.event_loop.to_options();
+                        quote! {
+                            let future = _rt.resolve(output);
+                            let output = _rt.with_event_loop_promise(future, #options).await;
+                        }
+                    }
+                    EventLoopPolicy::None => quote! {
+                        let output = ::ferrosaur::resolve_without_event_loop(_rt, output);
+                    },
+                };
+                let error_ty = match &self.throws {
+                    None => quote! { ::ferrosaur::Error },
+                    Some(throws) => quote! { ::ferrosaur::JsThrown<#throws> },
+                };
+                let await_output = match &self.throws {
+                    None => quote! {{
+                        #resolve
+                        output?
+                    }},
+                    Some(_) => quote! {{
+                        let promise = output.clone();
+                        #resolve
+                        match output {
+                            Ok(output) => output,
+                            Err(error) => {
+                                let scope = &mut _rt.handle_scope();
+                                let promise = v8::Local::new(scope, promise);
+                                return match promise.try_cast::<v8::Promise>() {
+                                    Ok(promise)
+                                        if promise.state() == v8::PromiseState::Rejected =>
+                                    {
+                                        let exception = promise.result(scope);
+                                        Err(::ferrosaur::JsThrown::from_exception(scope, exception))
+                                    }
+                                    _ => Err(::ferrosaur::Error::from(error).into()),
+                                };
+                            }
+                        }
+                    }},
+                };
+                let local_output = match &self.output {
+                    None => quote! {},
+                    Some(_) => quote! {
+                        let scope = &mut _rt.handle_scope();
+                        let output = v8::Local::new(scope, output);
+                    },
+                };
+                let writeback = if writebacks.is_empty() {
+                    quote! {}
+                } else {
+                    quote! {{
+                        let scope = &mut _rt.handle_scope();
+                        #(let #views = v8::Local::new(scope, #views);)*
+                        #(#updates)*
+                    }}
+                };
+                quote! {
+                    #abort_setup
+                    let (output, #(#views,)*) = {
+                        #invoke
+                        (
+                            v8::Global::new(scope, output),
+                            #(v8::Global::new(scope, #views),)*
+                        )
+                    };
+                    // the guards only cancel the tokens if this future is dropped while
+                    // awaiting, not if the promise is rejected
+                    #(let #abort_guards = #aborts.clone().drop_guard();)*
+                    let output = async {
+                        ::core::result::Result::<_, #error_ty>::Ok(#await_output)
+                    }
+                    .await;
+                    #(#abort_guards.disarm();)*
+                    let output = output?;
+                    #writeback
+                    #local_output
+                    #into_output
+                }
+            }
+        };
+
+        let return_ty = match self.intent {
+            FunctionIntent::Deferred => {
+                let output_ty = self.output_type();
+                parse_quote! { ::ferrosaur::JsPromise<#output_ty> }
+            }
+            _ => self.output_type(),
+        };
+
+        let return_ty = match &self.throws {
+            None => quote! { ::ferrosaur::Result<#return_ty> },
+            Some(throws) => quote! {
+                ::core::result::Result<#return_ty, ::ferrosaur::JsThrown<#throws>>
+            },
+        };
+
+        let timeout = match self.timeout {
+            Some(ms) => quote! { Some(::core::time::Duration::from_millis(#ms)) },
+            None => quote! { None },
+        };
+
+        let site = CallSite {
+            binding: fn_name,
+            path: match &self.source {
+                FunctionSource::Prop(prop) => Some(prop.to_name()),
+                FunctionSource::This => None,
+            },
+            asyncness: matches!(self.intent, FunctionIntent::Awaited(_)),
+        };
+
+        let body = site.wrap_with_timeout(&return_ty, timeout, body);
+
+        let inputs = &self.inputs;
+
+        let Generics {
+            params,
+            where_clause,
+            ..
+        } = fn_generics;
+
+        quote! {
+            #asyncness fn #fn_name <#params> (
+                #fn_self,
+                #(#inputs,)*
+                _rt: &mut JsRuntime,
+            ) -> #return_ty
+            #where_clause
+            {
+                #body
+            }
+        }
+    }
+}
+
+impl CallFunction {
+    fn output_type(&self) -> Type {
+        match &self.output {
+            None => Type::Tuple(TypeTuple {
+                paren_token: Paren(Span::call_site()),
+                elems: Default::default(),
+            }),
+            Some(ty) => ty.to_type(),
+        }
+    }
+}
+
+impl CallFunction {
+    pub fn from_sig(sig: &mut Signature) -> Caveat<Self> {
+        let mut errors = Error::accumulator();
+
+        let mut inputs = Vec::<FunctionInput>::new();
+
+        let mut this = FunctionThis::Self_;
+
+        sig.inputs = std::mem::take(&mut sig.inputs)
+            .into_iter()
+            .filter_map(|arg| {
+                let FnArg::Typed(arg) = arg else {
+                    return Some(arg);
+                };
+
+                let ty = V8Conv::from_type((*arg.ty).clone()).and_recover(&mut errors);
+
+                match *arg.pat {
+                    Pat::Ident(PatIdent {
+                        ref ident,
+                        by_ref: None,
+                        mutability: None,
+                        subpat: None,
+                        ..
+                    }) => {
+                        if ident == "this" {
+                            if let Some(undefined) = is_undefined(&arg) {
+                                this = undefined;
+                                return None;
+                            } else {
+                                this = FunctionThis::Unbound
+                            }
+                        }
+
+                        let spread = false;
+                        let ident = ident.clone();
+                        let arg = arg.tap_mut(|arg| arg.ty = ty.to_type().into());
+
+                        inputs.push(FunctionInput { ident, ty, spread });
+                        Some(FnArg::Typed(arg))
+                    }
+
+                    Pat::Range(PatRange {
+                        start: None,
+                        end: Some(ref end),
+                        limits: RangeLimits::HalfOpen(..),
+                        ..
+                    }) => match &**end {
+                        Expr::Path(path) if is_unit_path(path) => {
+                            let spread = true;
+                            let ident = path.path.segments[0].ident.clone();
+                            let arg = arg.tap_mut(|arg| {
+                                arg.pat = Pat::Ident(PatIdent {
+                                    attrs: vec![],
+                                    by_ref: None,
+                                    mutability: None,
+                                    ident: ident.clone(),
+                                    subpat: None,
+                                })
+                                .into();
+                                arg.ty = ty.to_type().into();
+                            });
+                            let ty = ty.for_spread();
+                            inputs.push(FunctionInput { ident, ty, spread });
+                            Some(FnArg::Typed(arg))
+                        }
+                        expr => {
+                            "spread argument should be written as `..name`\nfound extra patterns"
+                                .pipe(Error::custom)
+                                .with_span(expr)
+                                .pipe(|e| errors.push(e));
+                            Some(FnArg::Typed(arg))
+                        }
+                    },
+
+                    Pat::Range(PatRange {
+                        start: Some(ref start),
+                        ..
+                    }) => {
+                        "spread argument should be written as `..name`"
+                            .pipe(Error::custom)
+                            .with_span(start)
+                            .pipe(|e| errors.push(e));
+                        Some(FnArg::Typed(arg))
+                    }
+
+                    ref pat => {
+                        Error::custom("expected an identifier or spread argument")
+                            .with_span(pat)
+                            .pipe(|e| errors.push(e));
+                        Some(FnArg::Typed(arg))
+                    }
+                }
+            })
+            .collect();
+
+        let deferred = match &sig.output {
+            ReturnType::Type(_, ty) => deferred_output(ty),
+            ReturnType::Default => None,
+        };
+
+        let intent = match (sig.asyncness, &deferred) {
+            (Some(token), None) => FunctionIntent::Awaited(token),
+            (None, None) => FunctionIntent::Called,
+            (asyncness, Some(_)) => {
+                if let Some(token) = asyncness {
+                    "fn returning `JsPromise<...>` cannot be `async`\n\
+                    remove `async` to return the pending promise, \
+                    or return the value to await it immediately"
+                        .pipe(Error::custom)
+                        .with_span(&token)
+                        .pipe(|e| errors.push(e));
+                }
+                FunctionIntent::Deferred
+            }
+        };
+
+        let source = sig.ident.to_string().into();
+
+        let output = match deferred {
+            Some(Type::Tuple(TypeTuple { elems, .. })) if elems.is_empty() => None,
+            Some(ty) => V8Conv::from_output(ReturnType::Type(Default::default(), ty.into()))
+                .and_recover(&mut errors)
+                .pipe(Some),
+            None => match &sig.output {
+                ReturnType::Default => None,
+                output => V8Conv::from_output(output.clone())
+                    .and_recover(&mut errors)
+                    .pipe(Some),
+            },
+        };
+
+        let result = Self {
+            intent,
+            source,
+            this,
+            inputs,
+            output,
+            throws: None,
+            event_loop: Default::default(),
+            timeout: None,
+        };
+
+        let errors = errors.into_one();
+
+        (result, errors).into()
+    }
+}
+
+impl Default for EventLoopPolicy {
+    fn default() -> Self {
+        Self::Poll {
+            wait_for_inspector: None,
+            pump_v8_message_loop: None,
+        }
+    }
+}
+
+impl EventLoopPolicy {
+    fn to_options(&self) -> TokenStream {
+        let Self::Poll {
+            wait_for_inspector,
+            pump_v8_message_loop,
+        } = self
+        else {
+            return quote! { Default::default() };
+        };
+        if wait_for_inspector.is_none() && pump_v8_message_loop.is_none() {
+            return quote! { Default::default() };
+        }
+        let wait_for_inspector =
+            wait_for_inspector.map(|value| quote! { options.wait_for_inspector = #value; });
+        let pump_v8_message_loop =
+            pump_v8_message_loop.map(|value| quote! { options.pump_v8_message_loop = #value; });
+        quote! {{
+            let mut options = deno_core::PollEventLoopOptions::default();
+            #wait_for_inspector
+            #pump_v8_message_loop
+            options
+        }}
+    }
+}
+
+impl FunctionIntent {
+    pub fn only(self, sig: &Signature) -> Caveat<Self> {
+        let mut errors = Error::accumulator();
+
+        let color = Self::some(sig).and_recover(&mut errors);
+
+        match self {
+            Self::Called | Self::Constructed | Self::Deferred => {
+                if let Self::Awaited(span) = color {
+                    Error::custom("fn cannot be `async` here")
+                        .with_span(&span)
+                        .pipe(|e| errors.push(e));
+                }
+            }
+            Self::Awaited(_) => {
+                if !matches!(color, Self::Awaited(_)) {
+                    Error::custom("fn is required to be `async` here")
+                        .with_span(&sig.fn_token)
+                        .pipe(|e| errors.push(e));
+                }
+            }
+        }
+
+        (self, errors.into_one()).into()
+    }
+
+    pub fn some(sig: &Signature) -> Caveat<Self> {
+        let color = match &sig.asyncness {
+            None => Self::Called,
+            Some(token) => Self::Awaited(*token),
+        };
+        (color, Self::supported(sig)).into()
+    }
+
+    pub fn supported(sig: &Signature) -> Option<Error> {
+        let mut errors = Error::accumulator();
+
+        macro_rules! deny {
+            ($attr:ident, $msg:literal) => {
+                if sig.$attr.is_some() {
+                    Error::custom($msg)
+                        .with_span(&sig.$attr)
+                        .pipe(|e| errors.push(e));
+                }
+            };
+        }
+
+        deny!(constness, "fn cannot be `const` here");
+        deny!(unsafety, "fn cannot be `unsafe` here");
+        deny!(abi, "fn cannot be `extern` here");
+        deny!(variadic, "fn cannot be variadic here");
+
+        errors.into_one()
+    }
+}
+
+impl ToTokens for FunctionIntent {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let output = match self {
+            Self::Called | Self::Constructed | Self::Deferred => quote! {},
+            Self::Awaited(token) => quote! { #token },
+        };
+        tokens.extend(output);
+    }
+}
+
+impl ToTokens for FunctionInput {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let Self { ident, ty, .. } = self;
+        tokens.extend(quote! { #ident: #ty });
+    }
+}
+
+impl ToTokens for BindFunction {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let get_func = match &self.source {
+            FunctionSource::Prop(prop) => {
+                let unwrap_func = unwrap_v8_local("func");
+                let name = prop.to_name();
+                quote! {{
+                    let prop = #prop;
+                    let func = object.get(scope, prop.into());
+                    let func = #unwrap_func;
+                    func.try_cast::<v8::Function>()
+                        .map_err(|_| ::ferrosaur::Error::not_a_function(Some(#name)))?
+                }}
+            }
+            FunctionSource::This => quote! { object },
+        };
+
+        let object_ty = match &self.source {
+            FunctionSource::Prop(_) => quote! { v8::Object },
+            FunctionSource::This => quote! { v8::Function },
+        };
+
+        let object_err = match &self.source {
+            FunctionSource::Prop(_) => quote! { ::ferrosaur::Error::not_an_object() },
+            FunctionSource::This => quote! { ::ferrosaur::Error::not_a_function(None) },
+        };
+
+        let args_ty = match self.length {
+            FunctionLength::Fixed(len) => quote! {
+                [v8::Local<'a, v8::Value>; #len]
+            },
+            FunctionLength::Variadic => quote! {
+                Vec<v8::Local<'a, v8::Value>>
+            },
+        };
+
+        let recv = match self.this {
+            FunctionThis::Self_ => Some(quote! {
+                object.into()
+            }),
+            FunctionThis::Undefined { this, undefined } => {
+                let this = Ident::new("this", this);
+                let undefined = Ident::new("undefined", undefined);
+                Some(quote! {{
+                    let #this = v8::#undefined(scope);
+                    #this.into()
+                }})
+            }
+            FunctionThis::Unbound => None,
+        };
+
+        let args = match (recv, self.ctor) {
+            (_, true) => match self.this {
+                FunctionThis::Unbound => quote! {
+                    let (_, args) = args
+                        .split_first()
+                        .context("missing argument `this`")
+                        .map_err(::ferrosaur::Error::other)?;
+                },
+                FunctionThis::Self_ | FunctionThis::Undefined { .. } => quote! {
+                    let args = &args[..];
+                },
+            },
+            (Some(recv), false) => quote! {
+                let recv: v8::Local<v8::Value> = #recv;
+                let args = &args[..];
+            },
+            (None, false) => quote! {
+                let (recv, args) = args
+                    .split_first()
+                    .context("missing argument `this`")
+                    .map_err(::ferrosaur::Error::other)?;
+                let recv = *recv;
+            },
+        };
+
+        let retval = if self.ctor {
+            quote! {
+                func.new_instance(scope, args).map(Into::into)
+            }
+        } else {
+            quote! {
+                func.call(scope, recv, args)
+            }
+        };
+
+        let unwrap_retval = match &self.throws {
+            None => unwrap_v8_local("retval"),
+            Some(_) => quote! {{
+                let Some(retval) = retval else {
+                    return match scope.exception() {
+                        Some(exception) => {
+                            Err(::ferrosaur::JsThrown::from_exception(scope, exception))
+                        }
+                        None => Err(::ferrosaur::Error::other(anyhow!("retval is None")).into()),
+                    };
+                };
+                retval
+            }},
+        };
+
+        let return_ty = match &self.throws {
+            None => quote! { ::ferrosaur::Result<v8::Local<'a, v8::Value>> },
+            Some(throws) => quote! {
+                ::core::result::Result<v8::Local<'a, v8::Value>, ::ferrosaur::JsThrown<#throws>>
+            },
+        };
+
+        let result = quote! {
+            #[inline(always)]
+            fn call<'a, T>(
+                scope: &mut v8::HandleScope<'a>,
+                object: v8::Local<'a, T>,
+                #[allow(unused)]
+                args: #args_ty,
+            ) -> #return_ty
+            where
+                v8::Local<'a, T>: TryInto<v8::Local<'a, #object_ty>,
+                    Error: ::core::error::Error + Send + Sync + 'static>,
+            {
+                let scope = &mut v8::TryCatch::new(scope);
+                let object: v8::Local<#object_ty> = TryInto::try_into(object)
+                    .map_err(|_| #object_err)?;
+                let func: v8::Local<v8::Function> = #get_func;
+                #args
+                let retval: Option<v8::Local<v8::Value>> = #retval;
+                let retval = #unwrap_retval;
+                Ok(retval)
+            }
+        };
+
+        tokens.extend(result);
+    }
+}
+
+impl<T> From<T> for FunctionSource
+where
+    T: Into<PropertyKey>,
+{
+    fn from(value: T) -> Self {
+        Self::Prop(value.into())
+    }
+}
+
+fn argument_error(index: usize, name: &str) -> TokenStream {
+    quote! { |err| ::ferrosaur::Error::argument(#index, #name, err) }
+}
+
+#[inline(always)]
+fn is_undefined(arg: &PatType) -> Option<FunctionThis> {
+    let Type::Path(TypePath {
+        qself: None,
+        path: Path {
+            leading_colon: None,
+            ref segments,
+        },
+    }) = &*arg.ty
+    else {
+        return None;
+    };
+    if segments.len() != 1 || segments[0].ident != "undefined" || !segments[0].arguments.is_none() {
+        return None;
+    }
+    let this = arg.pat.span();
+    let undefined = arg.ty.span();
+    Some(FunctionThis::Undefined { this, undefined })
+}
+
+#[inline(always)]
+/// `JsPromise<T>` as a return type, which defers awaiting the promise to the caller.
+fn deferred_output(ty: &Type) -> Option<Type> {
+    let Type::Path(TypePath { qself: None, path }) = ty else {
+        return None;
+    };
+    let last = path.segments.last()?;
+    if last.ident != "JsPromise" {
+        return None;
+    }
+    let PathArguments::AngleBracketed(args) = &last.arguments else {
+        return None;
+    };
+    match args.args.iter().collect::<Vec<_>>()[..] {
+        [GenericArgument::Type(ty)] => Some(ty.clone()),
+        _ => None,
+    }
+}
+
+fn is_unit_path(path: &ExprPath) -> bool {
+    path.path.segments.len() == 1 && path.path.leading_colon.is_none() && path.qself.is_none()
+}
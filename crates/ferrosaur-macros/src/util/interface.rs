@@ -27,7 +27,17 @@ pub trait DeriveInterface {
     fn trait_type(item: TraitItemType) -> Result<SomeType>;
 
     fn derive_func(item: SomeFunc, this: OuterType) -> Result<TokenStream>;
-    fn derive_type(item: SomeType, this: OuterType) -> Result<TokenStream>;
+    fn derive_type(&self, item: SomeType, this: OuterType) -> Result<TokenStream>;
+
+    /// Derive all associated types at once, for items that need more than one of them.
+    fn derive_types(&self, items: Vec<SomeType>, this: OuterType) -> Result<TokenStream> {
+        let mut errors = Error::accumulator();
+        let types = items
+            .into_iter()
+            .filter_map(|item| errors.handle(self.derive_type(item, this)));
+        let types = quote! { #(#types)* };
+        errors.finish_with(types)
+    }
 
     fn count_items(fns: usize, types: usize) -> Result<()>;
     fn unsupported<T, S: Spanned>(span: S) -> Result<T>;
@@ -45,14 +55,14 @@ pub struct SomeType {
 }
 
 impl InterfaceLike {
-    pub fn derive<D: DeriveInterface>(self) -> Result<TokenStream> {
+    pub fn derive<D: DeriveInterface>(self, derive: &D) -> Result<TokenStream> {
         match self {
-            Self::Impl(item) => Self::derive_impl::<D>(item),
-            Self::Trait(item) => Self::derive_trait::<D>(item),
+            Self::Impl(item) => Self::derive_impl(item, derive),
+            Self::Trait(item) => Self::derive_trait(item, derive),
         }
     }
 
-    fn derive_impl<D: DeriveInterface>(item: ItemImpl) -> Result<TokenStream> {
+    fn derive_impl<D: DeriveInterface>(item: ItemImpl, derive: &D) -> Result<TokenStream> {
         let mut errors = Error::accumulator();
 
         errors.handle(only_inherent_impl(&item));
@@ -82,7 +92,6 @@ impl InterfaceLike {
                     .map(|item| funcs.push(item)),
                 ImplItem::Type(item) => errors
                     .handle(D::impl_type(item))
-                    .and_then(|item| errors.handle(D::derive_type(item, this)))
                     .map(|item| types.push(item)),
                 ImplItem::Verbatim(tokens) => {
                     let span = tokens.span();
@@ -102,6 +111,8 @@ impl InterfaceLike {
             .map_err(|err| err.with_span(&self_ty))
             .pipe(|err| errors.handle(err));
 
+        let types = errors.handle(derive.derive_types(types, this));
+
         let Generics {
             params,
             where_clause,
@@ -118,14 +129,14 @@ impl InterfaceLike {
                 impl <#params> #self_ty
                 #where_clause
                 {
-                    #(#types)*
+                    #types
                     #(#funcs)*
                 }
             };
         })
     }
 
-    fn derive_trait<D: DeriveInterface>(item: ItemTrait) -> Result<TokenStream> {
+    fn derive_trait<D: DeriveInterface>(item: ItemTrait, derive: &D) -> Result<TokenStream> {
         let mut errors = Error::accumulator();
 
         errors.handle(only_regular_trait(&item));
@@ -158,7 +169,6 @@ impl InterfaceLike {
                     .map(|item| funcs.push(item)),
                 TraitItem::Type(item) => errors
                     .handle(D::trait_type(item))
-                    .and_then(|item| errors.handle(D::derive_type(item, this)))
                     .map(|item| types.push(item)),
                 TraitItem::Verbatim(tokens) => {
                     let span = tokens.span();
@@ -178,6 +188,8 @@ impl InterfaceLike {
             .map_err(|err| err.with_span(&ident))
             .pipe(|err| errors.handle(err));
 
+        let types = errors.handle(derive.derive_types(types, this));
+
         let Generics {
             params,
             where_clause,
@@ -208,7 +220,7 @@ impl InterfaceLike {
                 #where_clause
                 {
                     #(#funcs)*
-                    #(#types)*
+                    #types
                 }
             }
         })
@@ -30,6 +30,15 @@ impl<K: AsRef<str>> From<K> for PropertyKey {
     }
 }
 
+impl PropertyKey {
+    pub fn to_name(&self) -> String {
+        match self {
+            Self::String(key) => key.clone(),
+            Self::Symbol(sym) => format!("{sym:?}"),
+        }
+    }
+}
+
 impl std::fmt::Debug for PropertyKey {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -63,11 +72,15 @@ impl ToTokens for PropertyKey {
             Self::String(key) => {
                 if key.is_ascii() {
                     quote! {
-                        ascii_str!(#key).v8_string(scope)?
+                        ascii_str!(#key)
+                            .v8_string(scope)
+                            .map_err(::ferrosaur::Error::other)?
                     }
                 } else {
                     quote! {
-                        FastString::from_static(#key).v8_string(scope)?
+                        FastString::from_static(#key)
+                            .v8_string(scope)
+                            .map_err(::ferrosaur::Error::other)?
                     }
                 }
             }
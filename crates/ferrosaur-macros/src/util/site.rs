@@ -0,0 +1,92 @@
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::{Ident, Signature};
+
+/// Attach a `ferrosaur::CallSite` to errors returned by a generated function.
+pub struct CallSite<'a> {
+    pub binding: &'a Ident,
+    pub path: Option<String>,
+    pub asyncness: bool,
+}
+
+/// `#[track_caller]` can only be applied to non-`async` functions.
+pub fn track_caller(sig: &Signature) -> TokenStream {
+    if sig.asyncness.is_some() {
+        quote! {}
+    } else {
+        quote! { #[track_caller] }
+    }
+}
+
+impl CallSite<'_> {
+    pub fn wrap<R: ToTokens>(&self, return_ty: R, body: TokenStream) -> TokenStream {
+        self.wrap_as(quote! { Self }, return_ty, body)
+    }
+
+    /// Like [`wrap`][Self::wrap], for code where `Self` is not the type that declares the binding.
+    pub fn wrap_as<T: ToTokens, R: ToTokens>(
+        &self,
+        this: T,
+        return_ty: R,
+        body: TokenStream,
+    ) -> TokenStream {
+        self.wrap_watched(this, return_ty, None, body)
+    }
+
+    /// Like [`wrap`][Self::wrap], and also arm a `ferrosaur::Watchdog` on `_rt` for the
+    /// duration of `body`.
+    pub fn wrap_with_timeout<R: ToTokens>(
+        &self,
+        return_ty: R,
+        timeout: TokenStream,
+        body: TokenStream,
+    ) -> TokenStream {
+        self.wrap_watched(quote! { Self }, return_ty, Some(timeout), body)
+    }
+
+    fn wrap_watched<T: ToTokens, R: ToTokens>(
+        &self,
+        this: T,
+        return_ty: R,
+        timeout: Option<TokenStream>,
+        body: TokenStream,
+    ) -> TokenStream {
+        let binding = self.binding.to_string();
+
+        let path = match &self.path {
+            Some(path) => quote! { Some(#path) },
+            None => quote! { None },
+        };
+
+        let location = if self.asyncness {
+            quote! { None }
+        } else {
+            quote! { Some(::core::panic::Location::caller()) }
+        };
+
+        let result = match (self.asyncness, timeout) {
+            (true, None) => quote! { async move { #body }.await },
+            (false, None) => quote! { (move || -> #return_ty { #body })() },
+            (true, Some(timeout)) => quote! {
+                ::ferrosaur::Watchdog::arm(_rt, #timeout)
+                    .guard(async move { #body })
+                    .await
+            },
+            (false, Some(timeout)) => quote! {{
+                let __watchdog = ::ferrosaur::Watchdog::arm(_rt, #timeout);
+                __watchdog.disarm((move || -> #return_ty { #body })())
+            }},
+        };
+
+        quote! {
+            let __site = ::ferrosaur::CallSite::new(
+                ::core::any::type_name::<#this>(),
+                #binding,
+                #path,
+                #location,
+            );
+            let __result: #return_ty = #result;
+            __result.map_err(|err| err.at(__site))
+        }
+    }
+}
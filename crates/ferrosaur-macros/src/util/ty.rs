@@ -0,0 +1,49 @@
+use darling::{Error, FromMeta, Result};
+use syn::{Expr, ExprPath, Lit, Meta, Type, TypePath};
+use tap::Pipe;
+
+#[derive(Debug, Clone)]
+pub struct TypeLike(pub Box<Type>);
+
+impl FromMeta for TypeLike {
+    fn from_meta(item: &Meta) -> Result<Self> {
+        match item {
+            Meta::Path(path) => Ok(Self::from_path(path.clone())),
+            Meta::List(list) => list
+                .parse_args::<Type>()?
+                .pipe(Box::new)
+                .pipe(Self)
+                .pipe(Ok),
+            Meta::NameValue(kv) => Self::from_expr(&kv.value),
+        }
+        .map_err(|e| e.with_span(item))
+    }
+
+    fn from_expr(expr: &Expr) -> Result<Self> {
+        match expr {
+            Expr::Path(ExprPath {
+                qself: None, path, ..
+            }) => Ok(Self::from_path(path.clone())),
+            Expr::Lit(lit) => Self::from_value(&lit.lit),
+            Expr::Group(group) => Self::from_expr(&group.expr),
+            expr => "expected a type"
+                .pipe(Error::custom)
+                .with_span(expr)
+                .pipe(Err),
+        }
+    }
+
+    fn from_value(value: &Lit) -> Result<Self> {
+        match value {
+            Lit::Str(s) => s.parse::<Type>()?.pipe(Box::new).pipe(Self).pipe(Ok),
+            _ => Err(Error::unexpected_lit_type(value)),
+        }
+        .map_err(|e| e.with_span(value))
+    }
+}
+
+impl TypeLike {
+    fn from_path(path: syn::Path) -> Self {
+        Self(Box::new(Type::Path(TypePath { qself: None, path })))
+    }
+}
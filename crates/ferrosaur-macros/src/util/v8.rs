@@ -1,5 +1,3 @@
-#![cfg_attr(doctest, doc = include_str!("../../../../docs/src/reference/typing.md"))]
-
 use darling::{Error, FromMeta, Result};
 use proc_macro2::{Span, TokenStream};
 use quote::{format_ident, quote, ToTokens};
@@ -17,19 +15,69 @@ use tap::{Pipe, Tap};
 
 use super::{type_ident, unwrap_v8_local, Caveat, MergeGenerics};
 
+use self::{
+    bigint::BigInt, buffer::Buffer, callback::Callback, primitive::Primitive, promise::Promise,
+    sequence::Sequence,
+};
+
+mod bigint;
+mod buffer;
+mod callback;
+mod date;
+mod primitive;
+mod promise;
+mod sequence;
+
 #[derive(Debug, Clone)]
 pub enum V8Conv {
     Trait { ty: Type },
     Value { ty: Type },
     Serde { ty: Type, hint: Ident },
+    Primitive { ty: Type, kind: Primitive },
+    Buffer { ty: Type, kind: Buffer },
+    BigInt { ty: Type, kind: BigInt },
+    Date { ty: Type },
+    Callback { ty: Type, kind: Callback },
+    Promise { ty: Type, kind: Promise },
+    Sequence { ty: Type, kind: Sequence },
+    Abort { ty: Type },
 }
 
 impl V8Conv {
     pub fn from_type(mut ty: Type) -> Caveat<Self> {
-        if let UseSerde::Some(hint) = fold_type_hint(&mut ty) {
+        if let Some(Caveat(kind, err)) = Callback::from_type(&mut ty) {
+            (Self::Callback { ty, kind }, err)
+        } else if let Some(Caveat(kind, err)) = Promise::from_type(&mut ty) {
+            (Self::Promise { ty, kind }, err)
+        } else if let Some(Caveat(kind, err)) = Sequence::from_type(&mut ty) {
+            (Self::Sequence { ty, kind }, err)
+        } else if let UseAbort(true) = fold_type_hint(&mut ty) {
+            (Self::Abort { ty }, None)
+        } else if let UseSerde::Some(hint) = fold_type_hint(&mut ty) {
             (Self::Serde { ty, hint }, None)
+        } else if let UseDate(true) = fold_type_hint(&mut ty) {
+            (Self::Date { ty }, None)
+        } else if let UseTypedArray(true) = fold_type_hint(&mut ty) {
+            match Buffer::from_type(&ty, true) {
+                Some(kind) => (Self::Buffer { ty, kind }, None),
+                None => {
+                    let err = "expected a `Vec` or slice of numbers in `typed_array<...>`"
+                        .pipe(Error::custom)
+                        .with_span(&ty);
+                    (Self::Trait { ty }, Some(err))
+                }
+            }
+        } else if let UseBigInt(true) = fold_type_hint(&mut ty) {
+            match BigInt::from_type(&ty) {
+                Ok(kind) => (Self::BigInt { ty, kind }, None),
+                Err(err) => (Self::Trait { ty }, Some(err)),
+            }
         } else if let Caveat(true, err) = has_v8_global(&ty) {
             (Self::Value { ty }, err)
+        } else if let Some(kind) = Primitive::from_type(&ty) {
+            (Self::Primitive { ty, kind }, None)
+        } else if let Some(kind) = Buffer::from_type(&ty, false) {
+            (Self::Buffer { ty, kind }, None)
         } else {
             (Self::Trait { ty }, None)
         }
@@ -55,7 +103,39 @@ impl V8Conv {
                 hint: format_ident!("serde"),
             })
             .into(),
-            ReturnType::Type(_, ty) => Self::from_type(*ty),
+            ReturnType::Type(_, ty) => match Self::from_type(*ty) {
+                Caveat(Self::Primitive { ty, kind }, _) if kind.is_borrowed() => {
+                    let err = Error::custom("cannot return a `&str`, use `String` instead")
+                        .with_span(&ty);
+                    (Self::Trait { ty }, err).into()
+                }
+                Caveat(Self::Buffer { ty, kind }, _) if kind.is_borrowed() => {
+                    let err = Error::custom("cannot return a borrowed slice, use `Vec<_>` instead")
+                        .with_span(&ty);
+                    (Self::Trait { ty }, err).into()
+                }
+                Caveat(Self::Callback { ty, .. }, _) => {
+                    let err = Error::custom("cannot return a Rust closure").with_span(&ty);
+                    (Self::Trait { ty }, err).into()
+                }
+                Caveat(Self::Promise { ty, .. }, _) => {
+                    let err =
+                        Error::custom("cannot return a `promise<...>`, use `async fn` instead")
+                            .with_span(&ty);
+                    (Self::Trait { ty }, err).into()
+                }
+                Caveat(Self::Sequence { ty, kind }, _) => {
+                    let err = format!("cannot return a `{}<...>`", kind.hint())
+                        .pipe(Error::custom)
+                        .with_span(&ty);
+                    (Self::Trait { ty }, err).into()
+                }
+                Caveat(Self::Abort { ty }, _) => {
+                    let err = Error::custom("cannot return an `abort<...>`").with_span(&ty);
+                    (Self::Trait { ty }, err).into()
+                }
+                ty => ty,
+            },
         }
     }
 
@@ -64,6 +144,22 @@ impl V8Conv {
             Self::Trait { ty } => ty,
             Self::Value { ty } => ty,
             Self::Serde { ty, .. } => ty,
+            Self::Primitive { ty, .. } => ty,
+            Self::Buffer { ty, .. } => ty,
+            Self::BigInt { ty, .. } => ty,
+            Self::Date { ty } => ty,
+            Self::Callback { ty, .. } => ty,
+            Self::Promise { ty, .. } => ty,
+            Self::Sequence { ty, .. } => ty,
+            Self::Abort { ty } => ty,
+        }
+    }
+
+    pub fn is_borrowed(&self) -> bool {
+        match self {
+            Self::Primitive { kind, .. } => kind.is_borrowed(),
+            Self::Buffer { kind, .. } => kind.is_borrowed(),
+            _ => false,
         }
     }
 
@@ -125,6 +221,16 @@ impl V8Conv {
                     #serde_v8::to_v8(#handle, #ident)
                 }}
             }
+            Self::Primitive { kind, .. } => kind.to_cast_into_v8(&ident, &handle),
+            Self::Buffer { kind, .. } => kind.to_cast_into_v8(&ident, &handle),
+            Self::BigInt { kind, .. } => kind.to_cast_into_v8(&ident, &handle),
+            Self::Date { .. } => date::to_cast_into_v8(&ident, &handle),
+            Self::Callback { ty, kind } => kind.to_cast_into_v8(ty, &ident, &handle),
+            Self::Promise { kind, .. } => kind.to_cast_into_v8(&ident, &handle),
+            Self::Sequence { kind, .. } => kind.to_cast_into_v8(&ident, &handle),
+            Self::Abort { .. } => quote! {{
+                ::ferrosaur::abort_signal(#handle, &#ident)
+            }},
         }
     }
 
@@ -150,6 +256,53 @@ impl V8Conv {
                     #serde_v8::from_v8(#handle, #ident)
                 }}
             }
+            Self::Primitive { kind, .. } => kind.to_cast_from_v8(&ident, &handle),
+            Self::Buffer { kind, .. } => kind.to_cast_from_v8(&ident, &handle),
+            Self::BigInt { kind, .. } => kind.to_cast_from_v8(&ident),
+            Self::Date { ty } => date::to_cast_from_v8(ty, &ident),
+            Self::Callback { ty, .. } => quote::quote_spanned! { ty.span() =>
+                compile_error!("cannot convert a JavaScript value into a Rust closure")
+            },
+            Self::Promise { ty, .. } => quote::quote_spanned! { ty.span() =>
+                compile_error!("cannot convert a JavaScript value into a Rust future")
+            },
+            Self::Sequence { ty, .. } => quote::quote_spanned! { ty.span() =>
+                compile_error!("cannot convert a JavaScript value into a Rust iterator or stream")
+            },
+            Self::Abort { ty } => quote::quote_spanned! { ty.span() =>
+                compile_error!("cannot convert a JavaScript value into a cancellation token")
+            },
+        }
+    }
+
+    pub fn to_writeback<K>(&self, name: K, scope: &'static str) -> Option<Writeback>
+    where
+        K: AsRef<str>,
+    {
+        let Self::Buffer { kind, .. } = self else {
+            return None;
+        };
+        let ident = format_ident!("{}", name.as_ref());
+        let handle = format_ident!("{scope}");
+        let view = format_ident!("__view_{}", name.as_ref());
+        let update = kind.to_writeback(&ident, &view)?;
+        let create = kind.to_view(&ident, &handle);
+        let ty = kind.view_type();
+        Some(Writeback {
+            view,
+            ty,
+            create,
+            update,
+        })
+    }
+
+    pub fn for_spread(self) -> Self {
+        match self {
+            Self::Primitive { ty, .. }
+            | Self::Buffer { ty, .. }
+            | Self::BigInt { ty, .. }
+            | Self::Date { ty } => Self::Trait { ty },
+            this => this,
         }
     }
 
@@ -166,22 +319,24 @@ impl V8Conv {
                 scope: &mut v8::HandleScope<'_a>,
                 this: _T,
                 prop: _K,
-            ) -> Result<#return_ty>
+            ) -> ::ferrosaur::Result<#return_ty>
             where
                 #bounds
             {
                 let scope = &mut v8::TryCatch::new(scope);
                 let this = TryInto::try_into(this)
-                    .context("failed to cast `self` as a v8::Object")?;
+                    .map_err(|_| ::ferrosaur::Error::not_an_object())?;
                 let prop = Into::into(prop);
                 let data = this.get(scope, prop);
                 let data = #unwrap_data;
-                Ok(#from_data?)
+                let data = #from_data
+                    .map_err(::ferrosaur::Error::output)?;
+                Ok(data)
             }
         }
     }
 
-    pub fn to_setter(&self, generics: &Generics) -> TokenStream {
+    pub fn to_setter(&self, generics: &Generics, index: usize, name: &str) -> TokenStream {
         let into_data = self.to_cast_into_v8("data", "scope");
         let data_type = self.to_type();
         let generics = Self::generics(generics);
@@ -194,14 +349,14 @@ impl V8Conv {
                 this: _T,
                 prop: _K,
                 data: #data_type
-            ) -> Result<()>
+            ) -> ::ferrosaur::Result<()>
             where
                 #bounds
             {
                 let data = #into_data
-                    .context("failed to convert into v8 value")?;
+                    .map_err(|err| ::ferrosaur::Error::argument(#index, #name, err))?;
                 let this = TryInto::try_into(this)
-                    .context("failed to cast `self` as a v8::Object")?;
+                    .map_err(|_| ::ferrosaur::Error::not_an_object())?;
                 let prop = Into::into(prop);
                 this.set(scope, prop, data);
                 Ok(())
@@ -209,7 +364,7 @@ impl V8Conv {
         }
     }
 
-    fn generics(outer: &Generics) -> MergeGenerics {
+    fn generics(outer: &Generics) -> MergeGenerics<'_> {
         MergeGenerics {
             outer,
             lifetimes: vec![quote! { '_a }],
@@ -262,6 +417,14 @@ impl V8Conv {
     }
 }
 
+#[derive(Debug, Clone)]
+pub struct Writeback {
+    pub view: Ident,
+    pub ty: TokenStream,
+    pub create: TokenStream,
+    pub update: TokenStream,
+}
+
 impl Default for V8Conv {
     fn default() -> Self {
         Self::new_v8("Value")
@@ -336,6 +499,50 @@ impl TypeHint for UseSerde {
     }
 }
 
+#[derive(Debug, Default, Clone)]
+struct UseBigInt(bool);
+
+impl TypeHint for UseBigInt {
+    const HINT: &'static str = "bigint";
+
+    fn on_type_hint(&mut self, _: &Ident) {
+        self.0 = true
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+struct UseAbort(bool);
+
+impl TypeHint for UseAbort {
+    const HINT: &'static str = "abort";
+
+    fn on_type_hint(&mut self, _: &Ident) {
+        self.0 = true
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+struct UseDate(bool);
+
+impl TypeHint for UseDate {
+    const HINT: &'static str = "date";
+
+    fn on_type_hint(&mut self, _: &Ident) {
+        self.0 = true
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+struct UseTypedArray(bool);
+
+impl TypeHint for UseTypedArray {
+    const HINT: &'static str = "typed_array";
+
+    fn on_type_hint(&mut self, _: &Ident) {
+        self.0 = true
+    }
+}
+
 trait TypeHint: Default {
     const HINT: &'static str;
     fn on_type_hint(&mut self, ident: &Ident);
@@ -0,0 +1,145 @@
+use darling::{Error, Result};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Ident, Path, Type, TypePath};
+
+#[derive(Debug, Clone)]
+pub enum BigInt {
+    I64,
+    U64,
+    I128,
+    U128,
+    Arbitrary(TypePath),
+}
+
+impl BigInt {
+    pub fn from_type(ty: &Type) -> Result<Self> {
+        let Type::Path(
+            type_path @ TypePath {
+                qself: None,
+                path: Path { segments, .. },
+            },
+        ) = ty
+        else {
+            return Err(Self::unsupported(ty));
+        };
+        if !segments.iter().all(|s| s.arguments.is_none()) {
+            return Err(Self::unsupported(ty));
+        }
+        let path = segments.iter().map(|s| s.ident.to_string());
+        let path = path.collect::<Vec<_>>();
+        match path.iter().map(String::as_str).collect::<Vec<_>>()[..] {
+            ["i64"] => Ok(Self::I64),
+            ["u64"] => Ok(Self::U64),
+            ["i128"] => Ok(Self::I128),
+            ["u128"] => Ok(Self::U128),
+            // only the full path, because a bare `BigInt` could be any type
+            [.., "num_bigint", "BigInt"] if cfg!(feature = "num-bigint") => {
+                Ok(Self::Arbitrary(type_path.clone()))
+            }
+            _ => Err(Self::unsupported(ty)),
+        }
+    }
+
+    fn unsupported(ty: &Type) -> Error {
+        #[cfg(feature = "num-bigint")]
+        let supported = "`i64`, `u64`, `i128`, `u128`, or `num_bigint::BigInt`";
+        #[cfg(not(feature = "num-bigint"))]
+        let supported = "`i64`, `u64`, `i128`, or `u128`";
+        Error::custom(format!("`bigint<...>` only supports {supported}")).with_span(ty)
+    }
+
+    pub fn to_cast_into_v8(&self, ident: &Ident, handle: &Ident) -> TokenStream {
+        let data = match self {
+            Self::I64 => quote! {
+                Ok(v8::BigInt::new_from_i64(#handle, #ident))
+            },
+            Self::U64 => quote! {
+                Ok(v8::BigInt::new_from_u64(#handle, #ident))
+            },
+            Self::I128 => quote! {{
+                let sign = #ident < 0;
+                let value = #ident.unsigned_abs();
+                let words = [value as u64, (value >> 64) as u64];
+                v8::BigInt::new_from_words(#handle, sign, &words)
+                    .context("failed to allocate a v8::BigInt")
+            }},
+            Self::U128 => quote! {{
+                let value = #ident;
+                let words = [value as u64, (value >> 64) as u64];
+                v8::BigInt::new_from_words(#handle, false, &words)
+                    .context("failed to allocate a v8::BigInt")
+            }},
+            Self::Arbitrary(ty) => quote! {{
+                let sign = #ident < <#ty>::default();
+                let words = #ident.magnitude().to_u64_digits();
+                v8::BigInt::new_from_words(#handle, sign, &words)
+                    .context("failed to allocate a v8::BigInt")
+            }},
+        };
+        quote! {{
+            let data: Result<v8::Local<v8::BigInt>> = #data;
+            data.map(Into::into)
+        }}
+    }
+
+    pub fn to_cast_from_v8(&self, ident: &Ident) -> TokenStream {
+        let data = match self {
+            Self::I64 => quote! {
+                match data.i64_value() {
+                    (data, true) => Ok(data),
+                    _ => Err(anyhow!("BigInt is out of range for `i64`")),
+                }
+            },
+            Self::U64 => quote! {
+                match data.u64_value() {
+                    (data, true) => Ok(data),
+                    _ => Err(anyhow!("BigInt is out of range for `u64`")),
+                }
+            },
+            Self::I128 => quote! {{
+                let mut words = [0; 2];
+                let count = data.word_count();
+                let (sign, _) = data.to_words_array(&mut words);
+                let value = words[0] as u128 | (words[1] as u128) << 64;
+                if count > 2 {
+                    Err(anyhow!("BigInt is out of range for `i128`"))
+                } else if sign {
+                    0i128
+                        .checked_sub_unsigned(value)
+                        .context("BigInt is out of range for `i128`")
+                } else {
+                    i128::try_from(value).context("BigInt is out of range for `i128`")
+                }
+            }},
+            Self::U128 => quote! {{
+                let mut words = [0; 2];
+                let count = data.word_count();
+                let (sign, _) = data.to_words_array(&mut words);
+                if count > 2 || sign {
+                    Err(anyhow!("BigInt is out of range for `u128`"))
+                } else {
+                    Ok(words[0] as u128 | (words[1] as u128) << 64)
+                }
+            }},
+            Self::Arbitrary(ty) => quote! {{
+                let mut words = vec![0; data.word_count()];
+                let (sign, words) = data.to_words_array(&mut words);
+                let mut bytes = words
+                    .iter()
+                    .flat_map(|word| word.to_le_bytes())
+                    .collect::<Vec<_>>();
+                bytes.push(0);
+                let value = <#ty>::from_signed_bytes_le(&bytes);
+                Ok(if sign { -value } else { value })
+            }},
+        };
+        quote! {{
+            let data: Result<_> = match #ident.try_cast::<v8::BigInt>() {
+                Err(err) => Err(err.into()),
+                Ok(data) => #data,
+            };
+            data
+        }}
+    }
+}
@@ -0,0 +1,203 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{GenericArgument, Ident, Path, PathArguments, Type, TypePath, TypeReference};
+
+#[derive(Debug, Clone)]
+pub enum Buffer {
+    Vec(Element),
+    Slice(Element),
+    SliceMut,
+    Bytes(TypePath),
+}
+
+#[derive(Debug, Clone)]
+pub struct Element {
+    ty: Ident,
+    class: Ident,
+}
+
+impl Buffer {
+    /// `Vec`s other than `Vec<u8>` are only recognized with `typed_array<...>`, so that
+    /// they keep using `FromV8` and `ToV8` by default.
+    pub fn from_type(ty: &Type, typed_array: bool) -> Option<Self> {
+        match ty {
+            Type::Path(
+                ty @ TypePath {
+                    qself: None,
+                    path:
+                        Path {
+                            leading_colon: None,
+                            segments,
+                        },
+                },
+            ) => match segments.len() {
+                1 if segments[0].ident == "Vec" => {
+                    let PathArguments::AngleBracketed(args) = &segments[0].arguments else {
+                        return None;
+                    };
+                    if args.args.len() != 1 {
+                        return None;
+                    }
+                    let GenericArgument::Type(ty) = &args.args[0] else {
+                        return None;
+                    };
+                    Element::from_type(ty)
+                        .filter(|elem| typed_array || elem.ty == "u8")
+                        .map(Self::Vec)
+                }
+                1 | 2 => {
+                    let path = segments.iter().map(|s| s.ident.to_string());
+                    let path = path.collect::<Vec<_>>();
+                    let args = segments.iter().all(|s| s.arguments.is_none());
+                    if args && (path == ["Bytes"] || path == ["bytes", "Bytes"]) {
+                        Some(Self::Bytes(ty.clone()))
+                    } else {
+                        None
+                    }
+                }
+                _ => None,
+            },
+            Type::Reference(TypeReference {
+                mutability, elem, ..
+            }) => {
+                let Type::Slice(slice) = &**elem else {
+                    return None;
+                };
+                let elem = Element::from_type(&slice.elem)?;
+                match mutability {
+                    None => Some(Self::Slice(elem)),
+                    Some(_) if elem.ty == "u8" => Some(Self::SliceMut),
+                    Some(_) => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    pub fn is_borrowed(&self) -> bool {
+        matches!(self, Self::Slice(_) | Self::SliceMut)
+    }
+
+    pub fn is_mutable(&self) -> bool {
+        matches!(self, Self::SliceMut)
+    }
+
+    pub fn to_cast_into_v8(&self, ident: &Ident, handle: &Ident) -> TokenStream {
+        let view = self.to_view(ident, handle);
+        quote! {{
+            let data: Result<v8::Local<v8::Value>> = #view.map(Into::into);
+            data
+        }}
+    }
+
+    pub fn to_view(&self, ident: &Ident, handle: &Ident) -> TokenStream {
+        let class = &self.element().class;
+        let data = match self {
+            Self::Vec(_) => quote! { #ident },
+            Self::Slice(_) | Self::SliceMut => quote! { #ident.to_vec() },
+            Self::Bytes(_) => quote! { Vec::<u8>::from(#ident) },
+        };
+        let error = format!("failed to create a {class}");
+        quote! {{
+            let data = #data;
+            let length = data.len();
+            let buffer = ::ferrosaur::new_array_buffer(#handle, data);
+            v8::#class::new(#handle, buffer, 0, length).context(#error)
+        }}
+    }
+
+    pub fn to_writeback(&self, ident: &Ident, view: &Ident) -> Option<TokenStream> {
+        if self.is_mutable() {
+            Some(quote! {
+                #view.copy_contents(#ident);
+            })
+        } else {
+            None
+        }
+    }
+
+    pub fn view_type(&self) -> TokenStream {
+        let class = &self.element().class;
+        quote! { v8::#class }
+    }
+
+    pub fn to_cast_from_v8(&self, ident: &Ident, handle: &Ident) -> TokenStream {
+        let this = match self {
+            Self::Slice(_) | Self::SliceMut => Self::Vec(self.element()),
+            _ => self.clone(),
+        };
+        let data = match this {
+            Self::Vec(Element { ty, class }) => {
+                let class = if ty == "u8" {
+                    format_ident!("ArrayBufferView")
+                } else {
+                    class
+                };
+                quote! {
+                    match #ident.try_cast::<v8::#class>() {
+                        Err(err) => Err(err.into()),
+                        Ok(data) => Ok(::ferrosaur::copy_from_view::<#ty>(data.into())),
+                    }
+                }
+            }
+            Self::Bytes(ty) => quote! {
+                match serde_v8::from_v8::<serde_v8::JsBuffer>(#handle, #ident) {
+                    Err(err) => Err(err.into()),
+                    Ok(data) => Ok(<#ty>::from_owner(data.into_parts())),
+                }
+            },
+            Self::Slice(_) | Self::SliceMut => unreachable!(),
+        };
+        quote! {{
+            let data: Result<_> = #data;
+            data
+        }}
+    }
+
+    fn element(&self) -> Element {
+        match self {
+            Self::Vec(elem) | Self::Slice(elem) => elem.clone(),
+            Self::SliceMut | Self::Bytes(_) => Element {
+                ty: format_ident!("u8"),
+                class: format_ident!("Uint8Array"),
+            },
+        }
+    }
+}
+
+impl Element {
+    fn from_type(ty: &Type) -> Option<Self> {
+        let Type::Path(TypePath {
+            qself: None,
+            path:
+                Path {
+                    leading_colon: None,
+                    segments,
+                },
+        }) = ty
+        else {
+            return None;
+        };
+        if segments.len() != 1 || !segments[0].arguments.is_none() {
+            return None;
+        }
+        let ty = &segments[0].ident;
+        let class = match ty.to_string().as_str() {
+            "u8" => "Uint8Array",
+            "i8" => "Int8Array",
+            "u16" => "Uint16Array",
+            "i16" => "Int16Array",
+            "u32" => "Uint32Array",
+            "i32" => "Int32Array",
+            "u64" => "BigUint64Array",
+            "i64" => "BigInt64Array",
+            "f32" => "Float32Array",
+            "f64" => "Float64Array",
+            _ => return None,
+        };
+        Some(Self {
+            ty: ty.clone(),
+            class: format_ident!("{class}"),
+        })
+    }
+}
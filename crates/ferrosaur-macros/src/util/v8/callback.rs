@@ -0,0 +1,175 @@
+use darling::Error;
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::{
+    Ident, Lifetime, ParenthesizedGenericArguments, PathArguments, ReturnType, TraitBound, Type,
+    TypeImplTrait, TypeParamBound,
+};
+use tap::Pipe;
+
+use super::V8Conv;
+use crate::util::{Caveat, RecoverableErrors};
+
+#[derive(Debug, Clone)]
+pub struct Callback {
+    inputs: Vec<V8Conv>,
+    output: Option<Box<V8Conv>>,
+}
+
+impl Callback {
+    /// Recognize `impl Fn(...) -> ...`, folding type hints in its arguments and return type.
+    pub fn from_type(ty: &mut Type) -> Option<Caveat<Self>> {
+        let Type::ImplTrait(TypeImplTrait { bounds, .. }) = ty else {
+            return None;
+        };
+
+        let mut errors = Error::accumulator();
+
+        let mut callback = None;
+        let mut is_static = false;
+
+        for bound in bounds.iter_mut() {
+            match bound {
+                TypeParamBound::Lifetime(lifetime) if lifetime.ident == "static" => {
+                    is_static = true;
+                }
+                TypeParamBound::Trait(TraitBound { path, .. }) => {
+                    let Some(last) = path.segments.last_mut() else {
+                        continue;
+                    };
+                    let PathArguments::Parenthesized(args) = &mut last.arguments else {
+                        continue;
+                    };
+                    if last.ident == "Fn" {
+                        callback = Some(Self::from_args(args).and_recover(&mut errors));
+                    } else if last.ident == "FnMut" || last.ident == "FnOnce" {
+                        "callback must be `Fn`, because it may be called more than once, \
+                        and from within itself"
+                            .pipe(Error::custom)
+                            .with_span(&last.ident)
+                            .pipe(|e| errors.push(e));
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let callback = callback?;
+
+        if !is_static {
+            bounds.push(TypeParamBound::Lifetime(Lifetime::new(
+                "'static",
+                Span::call_site(),
+            )));
+        }
+
+        Some((callback, errors.finish().err()).into())
+    }
+
+    fn from_args(args: &mut ParenthesizedGenericArguments) -> Caveat<Self> {
+        let mut errors = Error::accumulator();
+
+        let inputs = args
+            .inputs
+            .iter_mut()
+            .map(|ty| {
+                let conv = V8Conv::from_type(ty.clone()).and_recover(&mut errors);
+                if let Some(err) = Self::borrowed(&conv) {
+                    errors.push(err);
+                }
+                *ty = conv.to_type();
+                conv
+            })
+            .collect();
+
+        let output = match &mut args.output {
+            ReturnType::Default => None,
+            ReturnType::Type(_, ty) => {
+                let conv = V8Conv::from_output(ReturnType::Type(Default::default(), ty.clone()))
+                    .and_recover(&mut errors);
+                **ty = conv.to_type();
+                Some(Box::new(conv))
+            }
+        };
+
+        (Self { inputs, output }, errors.finish().err()).into()
+    }
+
+    fn borrowed(conv: &V8Conv) -> Option<Error> {
+        if let V8Conv::Callback { .. } = conv {
+            Error::custom("callback cannot receive another callback")
+                .with_span(conv.as_type())
+                .pipe(Some)
+        } else if conv.is_borrowed() {
+            Error::custom("callback arguments cannot be borrowed, use an owned type instead")
+                .with_span(conv.as_type())
+                .pipe(Some)
+        } else {
+            None
+        }
+    }
+
+    pub fn to_cast_into_v8(&self, ty: &Type, ident: &Ident, handle: &Ident) -> TokenStream {
+        let bounds = match ty {
+            Type::ImplTrait(TypeImplTrait { bounds, .. }) => bounds,
+            _ => unreachable!(),
+        };
+
+        let args = (0..self.inputs.len())
+            .map(|idx| format_ident!("arg{idx}"))
+            .collect::<Vec<_>>();
+
+        let casts = self
+            .inputs
+            .iter()
+            .zip(&args)
+            .enumerate()
+            .map(|(idx, (ty, arg))| {
+                let index = idx as i32;
+                let err = format!("failed to convert argument {idx} of callback");
+                let cast = ty.to_cast_from_v8(arg.to_string(), "scope");
+                quote! {
+                    let #arg = args.get(#index);
+                    let #arg = #cast.context(#err)?;
+                }
+            });
+
+        let output = match &self.output {
+            None => quote! {
+                let _: () = output;
+                Ok(v8::undefined(scope).into())
+            },
+            Some(ty) => {
+                let cast = ty.to_cast_into_v8("output", "scope");
+                quote! {
+                    let output: v8::Local<v8::Value> = #cast
+                        .context("failed to convert value returned by callback")?;
+                    Ok(output)
+                }
+            }
+        };
+
+        quote! {{
+            struct Callback<F>(F);
+
+            impl<F> ::ferrosaur::Callback for Callback<F>
+            where
+                F: #bounds,
+            {
+                fn call<'s>(
+                    &self,
+                    scope: &mut v8::HandleScope<'s>,
+                    args: &v8::FunctionCallbackArguments<'s>,
+                ) -> Result<v8::Local<'s, v8::Value>> {
+                    #(#casts)*
+                    let output = (self.0)(#(#args),*);
+                    #output
+                }
+            }
+
+            let data: Result<v8::Local<v8::Value>> =
+                ::ferrosaur::new_callback(#handle, Callback(#ident)).map(Into::into);
+            data
+        }}
+    }
+}
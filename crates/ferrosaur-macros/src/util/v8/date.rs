@@ -0,0 +1,30 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Ident, Type};
+
+pub fn to_cast_into_v8(ident: &Ident, handle: &Ident) -> TokenStream {
+    quote! {{
+        let data: Result<v8::Local<v8::Value>> =
+            match ::ferrosaur::convert::DateLike::to_millis(&#ident) {
+                None => Err(anyhow!("timestamp is out of range for a Date")),
+                Some(data) => v8::Date::new(#handle, data)
+                    .map(Into::into)
+                    .context("failed to create a v8::Date"),
+            };
+        data
+    }}
+}
+
+pub fn to_cast_from_v8(ty: &Type, ident: &Ident) -> TokenStream {
+    quote! {{
+        let data: Result<#ty> = match #ident.try_cast::<v8::Date>() {
+            Err(err) => Err(err.into()),
+            Ok(data) => match data.value_of() {
+                data if data.is_nan() => Err(anyhow!("Invalid Date")),
+                data => <#ty as ::ferrosaur::convert::DateLike>::from_millis(data)
+                    .with_context(|| format!("{} is out of range", data)),
+            },
+        };
+        data
+    }}
+}
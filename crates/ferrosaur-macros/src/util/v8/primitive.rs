@@ -0,0 +1,187 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Ident, Path, PathArguments, Type, TypePath, TypeReference};
+
+#[derive(Debug, Clone)]
+pub enum Primitive {
+    Int(Ident),
+    Uint(Ident),
+    Long(Ident),
+    Float(Ident),
+    Bool,
+    String,
+    Str,
+    Char,
+}
+
+impl Primitive {
+    pub fn from_type(ty: &Type) -> Option<Self> {
+        match ty {
+            Type::Path(ty) => {
+                let ident = single_ident(ty)?;
+                let primitive = match ident.to_string().as_str() {
+                    "i8" | "i16" | "i32" => Self::Int(ident.clone()),
+                    "u8" | "u16" | "u32" => Self::Uint(ident.clone()),
+                    "i64" | "u64" | "i128" | "u128" | "isize" | "usize" => {
+                        Self::Long(ident.clone())
+                    }
+                    "f32" | "f64" => Self::Float(ident.clone()),
+                    "bool" => Self::Bool,
+                    "String" => Self::String,
+                    "char" => Self::Char,
+                    _ => return None,
+                };
+                Some(primitive)
+            }
+            Type::Reference(TypeReference {
+                mutability: None,
+                elem,
+                ..
+            }) => match &**elem {
+                Type::Path(ty) if single_ident(ty)? == "str" => Some(Self::Str),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    pub fn is_borrowed(&self) -> bool {
+        matches!(self, Self::Str)
+    }
+
+    pub fn to_cast_into_v8(&self, ident: &Ident, handle: &Ident) -> TokenStream {
+        let data = match self {
+            Self::Int(_) => quote! {
+                Ok(v8::Integer::new(#handle, #ident as i32).into())
+            },
+            Self::Uint(_) => quote! {
+                Ok(v8::Integer::new_from_unsigned(#handle, #ident as u32).into())
+            },
+            Self::Long(_) => quote! {
+                match i64::try_from(#ident) {
+                    Ok(value) if value.unsigned_abs() <= MAX_SAFE_INTEGER => {
+                        Ok(v8::Number::new(#handle, value as f64).into())
+                    }
+                    _ => Err(anyhow!(
+                        "{} cannot be represented exactly as a JavaScript number",
+                        #ident
+                    )),
+                }
+            },
+            Self::Float(_) => quote! {
+                Ok(v8::Number::new(#handle, #ident as f64).into())
+            },
+            Self::Bool => quote! {
+                Ok(v8::Boolean::new(#handle, #ident).into())
+            },
+            Self::String | Self::Str => quote! {
+                v8::String::new(#handle, &#ident)
+                    .map(Into::into)
+                    .context("failed to allocate a v8::String")
+            },
+            Self::Char => quote! {
+                v8::String::new(#handle, #ident.encode_utf8(&mut [0; 4]))
+                    .map(Into::into)
+                    .context("failed to allocate a v8::String")
+            },
+        };
+        let max_safe_integer = match self {
+            Self::Long(_) => Some(max_safe_integer()),
+            _ => None,
+        };
+        quote! {{
+            #max_safe_integer
+            let data: Result<v8::Local<v8::Value>> = #data;
+            data
+        }}
+    }
+
+    pub fn to_cast_from_v8(&self, ident: &Ident, handle: &Ident) -> TokenStream {
+        let data = match self {
+            Self::Int(ty) | Self::Uint(ty) | Self::Long(ty) => {
+                let out_of_range = format!("{{}} is out of range for `{ty}`");
+                let max_safe_integer = max_safe_integer();
+                quote! {
+                    match #ident.try_cast::<v8::Number>() {
+                        Err(err) => Err(err.into()),
+                        Ok(data) => {
+                            #max_safe_integer
+                            let data = data.value();
+                            if data.fract() != 0.0 || data.abs() > MAX_SAFE_INTEGER as f64 {
+                                Err(anyhow!("{} is not a safe integer", data))
+                            } else {
+                                <#ty>::try_from(data as i64)
+                                    .map_err(|_| anyhow!(#out_of_range, data))
+                            }
+                        }
+                    }
+                }
+            }
+            Self::Float(ty) if ty == "f32" => quote! {
+                match #ident.try_cast::<v8::Number>() {
+                    Err(err) => Err(err.into()),
+                    Ok(data) => {
+                        let data = data.value();
+                        if data.is_finite() && (data as f32).is_infinite() {
+                            Err(anyhow!("{} is out of range for `f32`", data))
+                        } else {
+                            Ok(data as f32)
+                        }
+                    }
+                }
+            },
+            Self::Float(_) => quote! {
+                match #ident.try_cast::<v8::Number>() {
+                    Err(err) => Err(err.into()),
+                    Ok(data) => Ok(data.value()),
+                }
+            },
+            Self::Bool => quote! {
+                Ok(#ident.boolean_value(#handle))
+            },
+            Self::String | Self::Str => quote! {
+                Ok(#ident.to_rust_string_lossy(#handle))
+            },
+            Self::Char => quote! {
+                match #ident.try_cast::<v8::String>() {
+                    Err(err) => Err(err.into()),
+                    Ok(data) => {
+                        let data = data.to_rust_string_lossy(#handle);
+                        let mut chars = data.chars();
+                        match (chars.next(), chars.next()) {
+                            (Some(char), None) => Ok(char),
+                            _ => Err(anyhow!("expected a single character, got {:?}", data)),
+                        }
+                    }
+                }
+            },
+        };
+        quote! {{
+            let data: Result<_> = #data;
+            data
+        }}
+    }
+}
+
+fn single_ident(ty: &TypePath) -> Option<&Ident> {
+    let TypePath {
+        qself: None,
+        path: Path {
+            leading_colon: None,
+            segments,
+        },
+    } = ty
+    else {
+        return None;
+    };
+    if segments.len() != 1 || !matches!(segments[0].arguments, PathArguments::None) {
+        return None;
+    }
+    Some(&segments[0].ident)
+}
+
+fn max_safe_integer() -> TokenStream {
+    quote! {
+        const MAX_SAFE_INTEGER: u64 = (1 << 53) - 1;
+    }
+}
@@ -0,0 +1,94 @@
+use darling::Error;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{parse_quote, GenericArgument, Ident, Path, PathArguments, Type, TypePath};
+use tap::Pipe;
+
+use super::V8Conv;
+use crate::util::{Caveat, RecoverableErrors};
+
+#[derive(Debug, Clone)]
+pub struct Promise {
+    inner: Box<V8Conv>,
+}
+
+impl Promise {
+    /// Recognize `promise<T>`, and replace it with the type of futures that resolve to `T`.
+    pub fn from_type(ty: &mut Type) -> Option<Caveat<Self>> {
+        let Type::Path(TypePath {
+            qself: None,
+            path:
+                Path {
+                    leading_colon: None,
+                    segments,
+                },
+        }) = ty
+        else {
+            return None;
+        };
+
+        if segments.len() != 1 || segments[0].ident != "promise" {
+            return None;
+        }
+
+        let PathArguments::AngleBracketed(args) = &segments[0].arguments else {
+            return None;
+        };
+
+        let [GenericArgument::Type(inner)] = args.args.iter().collect::<Vec<_>>()[..] else {
+            return None;
+        };
+
+        let mut errors = Error::accumulator();
+
+        let inner = V8Conv::from_type(inner.clone()).and_recover(&mut errors);
+
+        if inner.is_borrowed() {
+            "promise<...> cannot resolve to a borrowed type, use an owned type instead"
+                .pipe(Error::custom)
+                .with_span(inner.as_type())
+                .pipe(|e| errors.push(e));
+        }
+
+        let output = inner.as_type();
+
+        *ty = parse_quote! {
+            impl ::core::future::Future<Output = deno_core::anyhow::Result<#output>> + 'static
+        };
+
+        let inner = Box::new(inner);
+
+        Some((Self { inner }, errors.finish().err()).into())
+    }
+
+    pub fn to_cast_into_v8(&self, ident: &Ident, handle: &Ident) -> TokenStream {
+        let resolve = impl_resolve(&self.inner, "failed to convert value resolved by future");
+        quote! {{
+            #resolve
+
+            let data: Result<v8::Local<v8::Value>> =
+                ::ferrosaur::new_promise::<Resolve, _, _>(#handle, #ident).map(Into::into);
+            data
+        }}
+    }
+}
+
+/// Declare a local `struct Resolve` that converts `inner` into a JavaScript value.
+pub fn impl_resolve(inner: &V8Conv, context: &str) -> TokenStream {
+    let output = inner.as_type();
+    let cast = inner.to_cast_into_v8("value", "scope");
+    quote! {
+        struct Resolve;
+
+        impl ::ferrosaur::Resolve<#output> for Resolve {
+            fn resolve<'s>(
+                scope: &mut v8::HandleScope<'s>,
+                value: #output,
+            ) -> Result<v8::Local<'s, v8::Value>> {
+                let data: v8::Local<v8::Value> = #cast
+                    .context(#context)?;
+                Ok(data)
+            }
+        }
+    }
+}
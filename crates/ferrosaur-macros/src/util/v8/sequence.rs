@@ -0,0 +1,122 @@
+use darling::Error;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{parse_quote, GenericArgument, Ident, Path, PathArguments, Type, TypePath};
+use tap::Pipe;
+
+use super::{promise::impl_resolve, V8Conv};
+use crate::util::{Caveat, RecoverableErrors};
+
+#[derive(Debug, Clone)]
+pub struct Sequence {
+    inner: Box<V8Conv>,
+    kind: SequenceKind,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum SequenceKind {
+    Iter,
+    Stream,
+}
+
+impl Sequence {
+    /// Recognize `js_iter<T>` and `js_stream<T>`, and replace them with the type of
+    /// iterators or streams that produce `T`.
+    pub fn from_type(ty: &mut Type) -> Option<Caveat<Self>> {
+        let Type::Path(TypePath {
+            qself: None,
+            path:
+                Path {
+                    leading_colon: None,
+                    segments,
+                },
+        }) = ty
+        else {
+            return None;
+        };
+
+        if segments.len() != 1 {
+            return None;
+        }
+
+        let kind = if segments[0].ident == "js_iter" {
+            SequenceKind::Iter
+        } else if segments[0].ident == "js_stream" {
+            SequenceKind::Stream
+        } else {
+            return None;
+        };
+
+        let PathArguments::AngleBracketed(args) = &segments[0].arguments else {
+            return None;
+        };
+
+        let [GenericArgument::Type(inner)] = args.args.iter().collect::<Vec<_>>()[..] else {
+            return None;
+        };
+
+        let mut errors = Error::accumulator();
+
+        let inner = V8Conv::from_type(inner.clone()).and_recover(&mut errors);
+
+        if inner.is_borrowed() {
+            format!(
+                "{}<...> cannot produce a borrowed type, use an owned type instead",
+                kind.hint()
+            )
+            .pipe(Error::custom)
+            .with_span(inner.as_type())
+            .pipe(|e| errors.push(e));
+        }
+
+        let item = inner.as_type();
+
+        *ty = match kind {
+            SequenceKind::Iter => parse_quote! {
+                impl ::core::iter::Iterator<Item = #item> + 'static
+            },
+            SequenceKind::Stream => parse_quote! {
+                impl deno_core::futures::Stream<Item = #item> + 'static
+            },
+        };
+
+        let inner = Box::new(inner);
+
+        Some((Self { inner, kind }, errors.finish().err()).into())
+    }
+
+    pub fn hint(&self) -> &'static str {
+        self.kind.hint()
+    }
+
+    pub fn to_cast_into_v8(&self, ident: &Ident, handle: &Ident) -> TokenStream {
+        let resolve = impl_resolve(&self.inner, self.kind.context());
+        let create = match self.kind {
+            SequenceKind::Iter => quote! { ::ferrosaur::new_iterator },
+            SequenceKind::Stream => quote! { ::ferrosaur::new_async_iterator },
+        };
+        quote! {{
+            #resolve
+
+            let data: Result<v8::Local<v8::Value>> =
+                #create::<Resolve, _, _>(#handle, #ident).map(Into::into);
+            data
+        }}
+    }
+}
+
+impl SequenceKind {
+    fn hint(self) -> &'static str {
+        match self {
+            Self::Iter => "js_iter",
+            Self::Stream => "js_stream",
+        }
+    }
+
+    fn context(self) -> &'static str {
+        match self {
+            Self::Iter => "failed to convert item produced by iterator",
+            Self::Stream => "failed to convert item produced by stream",
+        }
+    }
+}
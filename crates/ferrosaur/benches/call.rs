@@ -0,0 +1,80 @@
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+use deno_core::{serde_v8, v8, JsRuntime};
+
+#[path = "../tests/fixture/mod.rs"]
+mod fixture;
+
+use fixture::{
+    deno,
+    items::{modules::Main, traits::Shape, values::Rectangle},
+};
+
+const ITERATIONS: u32 = 100_000;
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() -> Result<()> {
+    let rt = &mut deno()?;
+
+    let main = Main::main_module_init(rt).await?;
+    let rectangle = main.rectangle(3.0, 4.0, rt)?;
+
+    let generated = measure(|| Ok(rectangle.area(rt)?))?;
+
+    let baseline = measure(|| bind_and_call(&rectangle, rt))?;
+
+    report("generated", generated);
+    report("baseline", baseline);
+
+    Ok(())
+}
+
+fn measure<F: FnMut() -> Result<f64>>(mut f: F) -> Result<Duration> {
+    for _ in 0..ITERATIONS / 10 {
+        f()?;
+    }
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        assert_eq!(f()?, 12.0);
+    }
+    Ok(start.elapsed())
+}
+
+/// What calling a method looked like before: `self` and the bound function
+/// are round-tripped through `v8::Global`s, each step in its own `HandleScope`.
+fn bind_and_call(rectangle: &Rectangle, rt: &mut JsRuntime) -> Result<f64> {
+    let object = {
+        let scope = &mut rt.handle_scope();
+        let object = v8::Local::new(scope, rectangle.as_ref());
+        v8::Global::new(scope, object)
+    };
+    let bound = {
+        let scope = &mut rt.handle_scope();
+        let object = v8::Local::new(scope, &object).try_cast::<v8::Object>()?;
+        let name = v8::String::new(scope, "area").context("string")?;
+        let func = object.get(scope, name.into()).context("get")?;
+        let func = func.try_cast::<v8::Function>()?;
+        let bind = v8::String::new(scope, "bind").context("string")?;
+        let bind = func.get(scope, bind.into()).context("get")?;
+        let bind = bind.try_cast::<v8::Function>()?;
+        let bound = bind.call(scope, func.into(), &[object.into()]);
+        let bound = bound.context("bind")?.try_cast::<v8::Function>()?;
+        v8::Global::new(scope, bound)
+    };
+    let output = {
+        let scope = &mut rt.handle_scope();
+        let func = v8::Local::new(scope, &bound);
+        let recv = v8::undefined(scope).into();
+        let output = func.call(scope, recv, &[]).context("call")?;
+        v8::Global::new(scope, output)
+    };
+    let scope = &mut rt.handle_scope();
+    let output = v8::Local::new(scope, output);
+    Ok(serde_v8::from_v8::<f64>(scope, output)?)
+}
+
+fn report(name: &str, elapsed: Duration) {
+    let per_call = elapsed / ITERATIONS;
+    println!("{name:>10}: {per_call:?}/call ({elapsed:?} total)");
+}
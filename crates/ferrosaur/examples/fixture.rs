@@ -18,6 +18,9 @@ pub mod modules {
 
     #[js(module("js/pkg.js", url("cargo:tests/js/pkg"), fast(unsafe_debug)))]
     pub struct Pkg;
+
+    #[js(module("js/host.js", fast))]
+    pub struct Host;
 }
 
 pub mod global {
@@ -42,6 +45,9 @@ pub mod values {
     #[js(value(of_type(v8::Function)))]
     pub(super) struct NavigateFn;
 
+    #[js(value(of_type(v8::Function)))]
+    pub struct Money;
+
     #[js(value)]
     pub struct Fibonacci;
 
@@ -50,6 +56,30 @@ pub mod values {
 
     #[js(value)]
     pub struct MessageIter;
+
+    #[js(value)]
+    pub struct Countdown;
+
+    #[js(value)]
+    pub struct Chunks;
+
+    #[js(value)]
+    pub struct Tickets;
+
+    #[js(value)]
+    pub struct Letters;
+
+    #[js(value)]
+    pub struct Primes;
+
+    #[js(value)]
+    pub struct Range;
+
+    #[js(value)]
+    pub struct Accumulate;
+
+    #[js(value)]
+    pub struct Numbers;
 }
 
 pub mod props {
@@ -68,6 +98,9 @@ pub mod props {
 
         #[js(prop)]
         pub fn height(&self) -> serde<f64> {}
+
+        #[js(prop(name = "height"))]
+        pub fn height_f64(&self) -> f64 {}
     }
 
     #[js(interface)]
@@ -87,13 +120,22 @@ pub mod props {
 }
 
 pub mod funcs {
+    use std::time::SystemTime;
+
     use deno_core::convert::OptionNull;
-    use ferrosaur::js;
+    use ferrosaur::{
+        convert::{ErrorObject, RegExp},
+        js, CancellationToken,
+    };
 
     use super::{
+        errors::ValidationFailure,
         global::Global,
         modules::{Cwd, I18n, Iter, Main},
-        values::{Console, Fibonacci, MessageIter, Messages, NavigateFn, Rectangle, ThisChecker},
+        values::{
+            Accumulate, Chunks, Console, Countdown, Fibonacci, Letters, MessageIter, Messages,
+            Money, NavigateFn, Numbers, Primes, Range, Rectangle, ThisChecker, Tickets,
+        },
     };
 
     #[js(interface)]
@@ -101,8 +143,116 @@ pub mod funcs {
         #[js(func)]
         pub async fn sleep(&self, value: bool, ms: serde<usize>) -> bool {}
 
+        #[js(func(name(sleep)))]
+        pub fn sleep_deferred(&self, value: bool, ms: usize) -> JsPromise<bool> {}
+
+        #[js(func(name(validateLater)))]
+        pub fn validate_deferred(&self, input: &str) -> JsPromise<String> {}
+
+        #[js(func(
+            name(sleep),
+            event_loop(wait_for_inspector, pump_v8_message_loop = false)
+        ))]
+        pub async fn sleep_quietly(&self, value: bool, ms: usize) -> bool {}
+
+        #[js(func(name(sleep), event_loop(none)))]
+        pub async fn sleep_now(&self, value: bool, ms: usize) -> bool {}
+
+        #[js(func(event_loop(none)))]
+        pub async fn increment(&self, value: u32) -> u32 {}
+
+        #[js(func(timeout_ms = 50))]
+        pub fn spin(&self) {}
+
+        #[js(func(name(spin)))]
+        pub fn spin_forever(&self) {}
+
+        #[js(func(timeout_ms = 50))]
+        pub async fn spin_later(&self) {}
+
+        #[js(func(name(sleep), timeout_ms = 20))]
+        pub async fn sleep_briefly(&self, value: bool, ms: usize) -> bool {}
+
+        #[js(func)]
+        pub async fn delay(&self, ms: usize, signal: abort<CancellationToken>) -> String {}
+
+        #[js(func(name(delay)))]
+        pub fn delay_deferred(
+            &self,
+            ms: usize,
+            signal: abort<CancellationToken>,
+        ) -> JsPromise<String> {
+        }
+
+        #[js(func)]
+        pub fn aborted_count(&self) -> usize {}
+
         #[js(func)]
         pub fn use_navigate(&self) -> NavigateFn {}
+
+        #[js(func)]
+        pub fn use_money(&self) -> Money {}
+
+        #[js(func)]
+        pub fn reverse(&self, bytes: &[u8]) -> Vec<u8> {}
+
+        #[js(func(name(reverse)))]
+        pub fn reverse_bytes(&self, bytes: bytes::Bytes) -> bytes::Bytes {}
+
+        #[js(func)]
+        pub fn fill(&self, bytes: &mut [u8], value: u8) {}
+
+        #[js(func)]
+        pub async fn fill_later(&self, bytes: &mut [u8], value: u8) {}
+
+        #[js(func)]
+        pub fn sum(&self, values: &[f32]) -> f64 {}
+
+        #[js(func)]
+        pub fn range(&self, n: u32) -> typed_array<Vec<f64>> {}
+
+        #[js(func)]
+        pub fn describe_error(&self, error: ErrorObject) -> serde<Vec<String>> {}
+
+        #[js(func)]
+        pub fn iso_string(&self, date: date<SystemTime>) -> String {}
+
+        #[js(func)]
+        pub fn matches(&self, regexp: RegExp, text: &str) -> bool {}
+
+        #[js(func(throws = ValidationFailure))]
+        pub fn validate(&self, input: &str) -> String {}
+
+        #[js(func(throws = ValidationFailure))]
+        pub async fn validate_later(&self, input: &str) -> String {}
+
+        #[js(func)]
+        pub fn map_numbers(
+            &self,
+            values: serde<Vec<u32>>,
+            f: impl Fn(u32, u32) -> u32,
+        ) -> serde<Vec<u32>> {
+        }
+
+        #[js(func(name(mapNumbers)))]
+        pub fn map_numbers_u64(
+            &self,
+            values: serde<Vec<u32>>,
+            f: impl Fn(u32) -> u64,
+        ) -> serde<Vec<f64>> {
+        }
+
+        #[js(func)]
+        pub fn for_each(&self, items: serde<Vec<String>>, f: impl Fn(serde<String>)) {}
+
+        #[js(func)]
+        pub async fn double_later(&self, value: promise<u32>) -> u32 {}
+
+        #[js(func)]
+        pub fn take_while_below(&self, values: js_iter<u32>, limit: u32) -> serde<Vec<u32>> {}
+
+        #[js(func)]
+        pub async fn join_lines(&self, lines: js_stream<serde<String>>) -> String {}
     }
 
     #[js(interface)]
@@ -127,6 +277,33 @@ pub mod funcs {
     impl Iter {
         #[js(func)]
         pub fn fibonacci(&self, iter: serde<usize>) -> Fibonacci {}
+
+        #[js(func)]
+        pub fn countdown(&self, from: u32) -> Countdown {}
+
+        #[js(func)]
+        pub fn chunks(&self, words: serde<Vec<String>>) -> Chunks {}
+
+        #[js(func)]
+        pub fn tickets(&self, count: u32) -> Tickets {}
+
+        #[js(func)]
+        pub fn closed_count(&self) -> u32 {}
+
+        #[js(func)]
+        pub fn letters(&self) -> Letters {}
+
+        #[js(func)]
+        pub fn primes(&self) -> Primes {}
+
+        #[js(func)]
+        pub fn range(&self, count: u32) -> Range {}
+
+        #[js(func)]
+        pub fn accumulate(&self, limit: u32) -> Accumulate {}
+
+        #[js(func)]
+        pub fn numbers(&self, count: u32, fail_at: i32) -> Numbers {}
     }
 
     #[js(interface)]
@@ -160,6 +337,54 @@ pub mod funcs {
 
         #[js(new(class(Date)))]
         pub fn date(&self, v: serde<f64>) -> v8::Global<v8::Value> {}
+
+        #[js(func(name(Number)))]
+        pub fn parse_u8(&self, v: &str) -> u8 {}
+
+        #[js(func(name(Number)))]
+        pub fn parse_i64(&self, v: &str) -> i64 {}
+
+        #[js(func(name(String)))]
+        pub fn from_u64(&self, v: u64) -> String {}
+
+        #[js(func(name(String)))]
+        pub fn from_char(&self, v: char) -> char {}
+
+        #[js(func(name(parseFloat)))]
+        pub fn parse_float(&self, v: &str) -> String {}
+
+        #[js(func(name(Number)))]
+        pub fn parse_bool(&self, v: &str) -> bool {}
+
+        #[js(func(name(BigInt)))]
+        pub fn parse_bigint_i64(&self, v: &str) -> bigint<i64> {}
+
+        #[js(func(name(BigInt)))]
+        pub fn parse_bigint_u64(&self, v: &str) -> bigint<u64> {}
+
+        #[js(func(name(String)))]
+        pub fn from_bigint_i128(&self, v: bigint<i128>) -> String {}
+
+        #[js(func(name(BigInt)))]
+        pub fn bigint_i128(&self, v: bigint<i128>) -> bigint<i128> {}
+
+        #[js(func(name(BigInt)))]
+        pub fn bigint_u128(&self, v: bigint<u128>) -> bigint<u128> {}
+
+        #[js(new(class(Date)))]
+        pub fn date_from(&self, v: date<SystemTime>) -> date<SystemTime> {}
+
+        #[js(new(class(Date)))]
+        pub fn date_parse(&self, v: &str) -> date<SystemTime> {}
+
+        #[js(new(class(RegExp)))]
+        pub fn regexp(&self, source: &str, flags: &str) -> RegExp {}
+
+        #[js(new(class(Error)))]
+        pub fn error(&self, message: &str, options: v8::Global<v8::Value>) -> ErrorObject {}
+
+        #[js(func(name(__undefined__)))]
+        pub fn undefined_func(&self) {}
     }
 
     #[js(interface)]
@@ -181,6 +406,32 @@ pub mod funcs {
     }
 }
 
+pub mod errors {
+    use ferrosaur::{convert::ErrorObject, js};
+
+    #[js(error)]
+    #[derive(Debug)]
+    pub enum ValidationFailure {
+        #[js(name = "ValidationError")]
+        Invalid {
+            field: String,
+            message: String,
+        },
+        RangeError(ErrorObject),
+    }
+
+    impl std::fmt::Display for ValidationFailure {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                Self::Invalid { field, message } => write!(f, "invalid {field}: {message}"),
+                Self::RangeError(error) => error.fmt(f),
+            }
+        }
+    }
+
+    impl std::error::Error for ValidationFailure {}
+}
+
 pub mod ctors {
     use ferrosaur::js;
 
@@ -202,30 +453,83 @@ pub mod ctors {
 pub mod protocols {
     use ferrosaur::js;
 
-    use super::values::{Fibonacci, NavigateFn};
+    use super::values::{
+        Accumulate, Countdown, Fibonacci, Messages, Money, NavigateFn, Numbers, Primes, Range,
+        Tickets,
+    };
 
     #[js(callable)]
     impl NavigateFn {
         pub fn call(&self, path: serde<&str>) {}
     }
 
+    #[js(callable)]
+    impl Money {
+        pub fn call_with_amount(&self, amount: f64, currency: &str) -> String {}
+
+        pub fn call_with_money(&self, money: serde<serde_json::Value>) -> String {}
+
+        #[js(new)]
+        pub fn new_money(&self, amount: f64, currency: &str) -> serde<serde_json::Value> {}
+    }
+
     #[js(iterator)]
     impl Fibonacci {
         type Item = serde<usize>;
     }
+
+    #[js(async_iterator)]
+    impl Countdown {
+        type Item = u32;
+    }
+
+    #[js(iterator)]
+    impl Tickets {
+        type Item = i32;
+    }
+
+    #[js(iterable)]
+    impl Messages {
+        type Item = serde<(String, String)>;
+    }
+
+    #[js(iterable)]
+    impl Primes {
+        type Item = u32;
+    }
+
+    #[js(iterable)]
+    impl Range {
+        type Item = u32;
+    }
+
+    #[js(iterator(batch = 4))]
+    impl Numbers {
+        type Item = u32;
+    }
+
+    #[js(generator)]
+    impl Accumulate {
+        type Yield = u32;
+        type Return = String;
+        type Next = u32;
+    }
 }
 
 pub mod traits {
     use ferrosaur::js;
     use serde::de::DeserializeOwned;
 
-    use super::values::{MessageIter, Rectangle};
+    use super::values::{Chunks, Letters, MessageIter, Rectangle};
 
     #[js(interface)]
     pub trait Shape {
         #[js(func)]
         fn area(&self) -> serde<f64>;
 
+        #[js(func(name(area)))]
+        fn area_f64(&self) -> f64;
+
         #[js(func(Symbol(toPrimitive)))]
         fn value(&self) -> serde<serde_json::Value>;
     }
@@ -242,6 +546,23 @@ pub mod traits {
     }
 
     impl Entries<String, String> for MessageIter {}
+
+    #[js(async_iterator)]
+    pub trait ByteChunks: Sized {
+        type Item = serde<Vec<u8>>;
+    }
+
+    impl ByteChunks for Chunks {}
+
+    #[js(iterable)]
+    pub trait Listing<T>: Sized
+    where
+        T: DeserializeOwned,
+    {
+        type Item = serde<T>;
+    }
+
+    impl Listing<String> for Letters {}
 }
 
 pub mod indexing {
@@ -253,6 +574,9 @@ pub mod indexing {
     impl I18n {
         #[js(get_index)]
         pub fn i18n(&self, key: serde<&str>) -> serde<String> {}
+
+        #[js(get_index)]
+        pub fn i18n_str(&self, key: &str) -> String {}
     }
 
     #[js(interface)]
@@ -265,8 +589,156 @@ pub mod indexing {
     }
 }
 
+pub mod classes {
+    use ferrosaur::js;
+
+    use super::modules::Main;
+
+    #[js(class)]
+    pub struct Counter {
+        count: u32,
+    }
+
+    #[js(export)]
+    impl Counter {
+        #[js(constructor)]
+        pub fn new(start: u32) -> Self {
+            Self { count: start }
+        }
+
+        #[js(method)]
+        pub fn increment(&mut self, by: u32) -> u32 {
+            self.count += by;
+            self.count
+        }
+
+        #[js(method)]
+        pub fn value(&self) -> u32 {
+            self.count
+        }
+
+        #[js(method)]
+        pub fn fork(&self) -> Counter {
+            Self { count: self.count }
+        }
+
+        #[js(method(name = "withLabel"))]
+        pub fn label(label: String) -> String {
+            format!("Counter({label})")
+        }
+
+        pub fn count(&self) -> u32 {
+            self.count
+        }
+    }
+
+    #[js(interface)]
+    impl Main {
+        #[js(func)]
+        pub fn count_to(&self, class: v8::Global<v8::Function>, limit: u32) -> u32 {}
+
+        #[js(func)]
+        pub fn bump_counter(&self, counter: Counter) -> u32 {}
+    }
+}
+
+pub mod hosts {
+    use ferrosaur::js;
+
+    use super::modules::Main;
+
+    #[js(interface(host(LoggerHost)))]
+    pub trait Logger {
+        #[js(func)]
+        fn log(&self, message: String);
+
+        #[js(prop(with_setter))]
+        fn prefix(&self) -> String;
+    }
+
+    #[js(value(of_type(v8::Object)))]
+    pub struct HostLogger;
+
+    impl Logger for HostLogger {}
+
+    #[js(interface)]
+    impl Main {
+        #[js(func)]
+        pub fn use_logger(&self, logger: HostLogger, messages: serde<Vec<String>>) -> String {}
+    }
+}
+
+pub mod exports {
+    use ferrosaur::js;
+
+    use super::modules::Host;
+
+    #[js(host_module("host:fixture"))]
+    #[allow(unused, reason = "used in tests that add this extension")]
+    pub mod host {
+        use ferrosaur::js;
+
+        #[js(export)]
+        fn add(a: f64, b: f64) -> f64 {
+            a + b
+        }
+
+        #[js(export(name = "greet"))]
+        fn greeting(name: String) -> String {
+            format!("Hello, {name}!")
+        }
+
+        #[js(export)]
+        fn read(path: String) -> Result<String, String> {
+            match path.as_str() {
+                "greeting.txt" => Ok("hello".into()),
+                _ => Err(format!("no such file: {path}")),
+            }
+        }
+
+        #[js(class)]
+        pub struct Tally {
+            total: f64,
+        }
+
+        #[js(export)]
+        impl Tally {
+            #[js(constructor)]
+            fn new() -> Self {
+                Self { total: 0.0 }
+            }
+
+            // argument names that the generated code also uses
+            #[js(method)]
+            fn add(&mut self, scope: f64, args: f64) {
+                self.total += scope * args;
+            }
+
+            #[js(method)]
+            fn total(&self) -> f64 {
+                self.total
+            }
+        }
+    }
+
+    #[js(interface)]
+    impl Host {
+        #[js(func)]
+        pub fn greet_sum(&self, a: f64, b: f64, name: String) -> String {}
+
+        #[js(func)]
+        pub fn tally(&self, values: serde<Vec<f64>>) -> f64 {}
+
+        #[js(func)]
+        pub fn read_or_error(&self, path: String) -> String {}
+
+        #[js(func)]
+        pub fn exports(&self) -> serde<Vec<String>> {}
+    }
+}
+
 pub fn modules() -> Result<StaticModuleLoader> {
-    use self::modules::{Cwd, I18n, Iter, Main, Pkg};
+    use self::modules::{Cwd, Host, I18n, Iter, Main, Pkg};
 
     Ok(StaticModuleLoader::new([
         Main::preloaded()?,
@@ -274,6 +746,7 @@ pub fn modules() -> Result<StaticModuleLoader> {
         I18n::preloaded()?,
         Cwd::preloaded()?,
         Pkg::preloaded()?,
+        Host::preloaded()?,
     ]))
 }
 
@@ -0,0 +1,39 @@
+import * as fixture from "host:fixture";
+import { add, greet, read, Tally } from "host:fixture";
+
+/**
+ * @param {number} a
+ * @param {number} b
+ * @param {string} name
+ * @returns {string}
+ */
+export const greetSum = (a, b, name) => `${greet(name)} ${add(a, b)}`;
+
+/**
+ * @param {number[]} values
+ * @returns {number}
+ */
+export const tally = (values) => {
+  const tally = new Tally();
+  for (const value of values) {
+    tally.add(value, 1);
+  }
+  return tally.total();
+};
+
+/**
+ * @param {string} path
+ * @returns {string}
+ */
+export const readOrError = (path) => {
+  try {
+    return read(path);
+  } catch (error) {
+    return `${error.name}: ${error.message}`;
+  }
+};
+
+/**
+ * @returns {string[]}
+ */
+export const exports = () => Object.keys(fixture).sort();
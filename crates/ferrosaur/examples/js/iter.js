@@ -9,3 +9,93 @@ export function* fibonacci(iter) {
     [a, b] = [b, a + b];
   }
 }
+
+/**
+ * @param {number} from
+ */
+export async function* countdown(from) {
+  for (let i = from; i > 0; i--) {
+    await new Promise((resolve) => setTimeout(resolve, 0));
+    yield i;
+  }
+}
+
+/**
+ * @param {string[]} words ASCII words
+ */
+export async function* chunks(words) {
+  for (const word of words) {
+    yield Array.from(word, (c) => c.charCodeAt(0));
+  }
+}
+
+let closed = 0;
+
+/**
+ * @param {number} count
+ */
+export function* tickets(count) {
+  try {
+    for (let i = 1; i <= count; i++) {
+      try {
+        yield i;
+      } catch {
+        yield -i;
+      }
+    }
+  } finally {
+    closed += 1;
+  }
+}
+
+export const closedCount = () => closed;
+
+export const letters = () => ["a", "b", "c"];
+
+export const primes = () => new Set([2, 3, 5, 7]);
+
+/**
+ * @param {number} count
+ */
+export const range = (count) => ({
+  *[Symbol.iterator]() {
+    try {
+      for (let i = 0; i < count; i++) {
+        yield i;
+      }
+    } finally {
+      closed += 1;
+    }
+  },
+});
+
+/**
+ * @param {number} limit
+ * @returns {Generator<number, string, number>}
+ */
+export function* accumulate(limit) {
+  let total = 0;
+  let count = 0;
+  while (total < limit) {
+    total += yield total;
+    count += 1;
+  }
+  return `${count} values`;
+}
+
+/**
+ * @param {number} count
+ * @param {number} failAt
+ */
+export function* numbers(count, failAt) {
+  try {
+    for (let i = 0; i < count; i++) {
+      if (i === failAt) {
+        throw new Error(`failed at ${i}`);
+      }
+      yield i;
+    }
+  } finally {
+    closed += 1;
+  }
+}
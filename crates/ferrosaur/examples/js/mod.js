@@ -7,11 +7,70 @@
 export const sleep = (v, ms) =>
   new Promise((resolve) => setTimeout(() => resolve(v), ms));
 
+/**
+ * @param {number} value
+ * @returns {Promise<number>}
+ */
+export const increment = async (value) => (await Promise.resolve(value)) + 1;
+
+export const spin = () => {
+  for (;;);
+};
+
+export const spinLater = async () => {
+  await sleep(null, 0);
+  spin();
+};
+
+let aborted = 0;
+
+/**
+ * @param {number} ms
+ * @param {AbortSignal} signal
+ * @returns {Promise<string>}
+ */
+export const delay = (ms, signal) =>
+  new Promise((resolve, reject) => {
+    signal.throwIfAborted();
+    const timer = setTimeout(() => resolve("done"), ms);
+    signal.addEventListener("abort", () => {
+      aborted += 1;
+      clearTimeout(timer);
+      reject(signal.reason);
+    });
+  });
+
+export const abortedCount = () => aborted;
+
 /**
  * @returns {(path: string) => void}
  */
 export const useNavigate = () => (path) => console.log("navigating to", path);
 
+/**
+ * @typedef {{ amount: number; currency: string }} MoneyLike
+ */
+
+/**
+ * Formats an amount, or a {@link MoneyLike}, when called; creates one when constructed.
+ *
+ * @param {number | MoneyLike} amount
+ * @param {string} [currency]
+ */
+function Money(amount, currency = "USD") {
+  if (new.target) {
+    this.amount = amount;
+    this.currency = currency;
+    return;
+  }
+  if (typeof amount === "object") {
+    return `${amount.amount.toFixed(2)} ${amount.currency}`;
+  }
+  return `${amount.toFixed(2)} ${currency}`;
+}
+
+export const useMoney = () => Money;
+
 class Shape {
   /**
    * @returns {number}
@@ -73,3 +132,188 @@ export class ThisConsideredHarmful {
     return this;
   }
 }
+
+/**
+ * @param {Uint8Array} bytes
+ * @returns {Uint8Array}
+ */
+export const reverse = (bytes) => bytes.slice().reverse();
+
+/**
+ * @param {Uint8Array} bytes
+ * @param {number} value
+ */
+export const fill = (bytes, value) => {
+  bytes.fill(value);
+};
+
+/**
+ * @param {Uint8Array} bytes
+ * @param {number} value
+ */
+export const fillLater = async (bytes, value) => {
+  await sleep(null, 0);
+  bytes.fill(value);
+};
+
+/**
+ * @param {Float32Array} values
+ * @returns {number}
+ */
+export const sum = (values) => values.reduce((a, b) => a + b, 0);
+
+/**
+ * @param {number} n
+ * @returns {Float64Array}
+ */
+export const range = (n) => Float64Array.from({ length: n }, (_, i) => i / 2);
+
+/**
+ * @param {Error} error
+ * @returns {string[]}
+ */
+export const describeError = (error) => {
+  const chain = [];
+  for (let e = error; e instanceof Error; e = e.cause) {
+    chain.push(`${e.name}: ${e.message}`);
+  }
+  return chain;
+};
+
+/**
+ * @param {Date} date
+ * @returns {string}
+ */
+export const isoString = (date) => date.toISOString();
+
+/**
+ * @param {RegExp} regexp
+ * @param {string} text
+ * @returns {boolean}
+ */
+export const matches = (regexp, text) => regexp.test(text);
+
+export class ValidationError extends Error {
+  /**
+   * @param {string} field
+   * @param {string} message
+   */
+  constructor(field, message) {
+    super(message);
+    this.field = field;
+  }
+}
+
+export class MissingInputError extends ValidationError {}
+
+/**
+ * @param {string} input
+ * @returns {string}
+ */
+export const validate = (input) => {
+  if (!input) {
+    throw new ValidationError("input", "input is required");
+  }
+  if (input === "missing") {
+    throw new MissingInputError("input", "input is missing");
+  }
+  if (input.length > 8) {
+    throw new RangeError("input is too long");
+  }
+  if (input === "null") {
+    throw null;
+  }
+  return input.toUpperCase();
+};
+
+/**
+ * @param {string} input
+ * @returns {Promise<string>}
+ */
+export const validateLater = async (input) => {
+  await new Promise((resolve) => setTimeout(resolve, 0));
+  return validate(input);
+};
+
+/**
+ * @param {number[]} values
+ * @param {(value: number, index: number) => number} fn
+ * @returns {number[]}
+ */
+export const mapNumbers = (values, fn) => values.map((v, i) => fn(v, i));
+
+/**
+ * @param {string[]} items
+ * @param {(item: string) => void} fn
+ */
+export const forEach = (items, fn) => items.forEach((item) => fn(item));
+
+/**
+ * @param {Promise<number>} value
+ * @returns {Promise<number>}
+ */
+export const doubleLater = async (value) => (await value) * 2;
+
+/**
+ * @param {Iterable<number>} values
+ * @param {number} limit
+ * @returns {number[]}
+ */
+export const takeWhileBelow = (values, limit) => {
+  const taken = [];
+  for (const value of values) {
+    if (value >= limit) {
+      break;
+    }
+    taken.push(value);
+  }
+  return taken;
+};
+
+/**
+ * @param {AsyncIterable<string>} lines
+ * @returns {Promise<string>}
+ */
+export const joinLines = async (lines) => {
+  const joined = [];
+  for await (const line of lines) {
+    joined.push(line);
+  }
+  return joined.join("\n");
+};
+
+/**
+ * @typedef {{ increment(by: number): number; value(): number }} Counter
+ */
+
+/**
+ * @param {new (start: number) => Counter} Counter
+ * @param {number} limit
+ * @returns {number}
+ */
+export const countTo = (Counter, limit) => {
+  const counter = new Counter(0);
+  while (counter.value() < limit) {
+    counter.increment(1);
+  }
+  return counter.value();
+};
+
+/**
+ * @param {Counter} counter
+ * @returns {number}
+ */
+export const bumpCounter = (counter) => counter.increment(10);
+
+/**
+ * @param {{ log(message: string): void; prefix: string }} logger
+ * @param {string[]} messages
+ * @returns {string}
+ */
+export const useLogger = (logger, messages) => {
+  logger.prefix = `${logger.prefix}[js] `;
+  for (const message of messages) {
+    logger.log(message);
+  }
+  return logger.prefix;
+};
@@ -0,0 +1,174 @@
+use std::{
+    cell::{Cell, RefCell},
+    fmt,
+    rc::{Rc, Weak},
+};
+
+use deno_core::{
+    anyhow::{self, anyhow, Context},
+    v8, JsRuntime, V8TaskSpawner,
+};
+
+/// A token for cancelling JavaScript work, passed to functions as `abort<CancellationToken>`.
+///
+/// Each call creates an `AbortController` and passes its `AbortSignal` to JavaScript. The
+/// signal is aborted when the token is [cancelled][Self::cancel], and, for `async`
+/// functions, when the returned future is dropped before it completes.
+///
+/// Clones refer to the same token.
+#[derive(Clone, Default)]
+pub struct CancellationToken(Rc<Inner>);
+
+#[derive(Default)]
+struct Inner {
+    cancelled: Cell<bool>,
+    children: RefCell<Vec<Weak<Inner>>>,
+    callbacks: RefCell<Vec<Box<dyn FnOnce()>>>,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancel this token and all of its children.
+    ///
+    /// Signals are aborted the next time the event loop is polled.
+    pub fn cancel(&self) {
+        self.0.cancel()
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.cancelled.get()
+    }
+
+    /// Create a token that is cancelled when this token is, but can also be cancelled
+    /// on its own.
+    pub fn child_token(&self) -> Self {
+        let child = Self::new();
+        if self.is_cancelled() {
+            child.cancel();
+        } else {
+            let mut children = self.0.children.borrow_mut();
+            children.retain(|child| child.strong_count() > 0);
+            children.push(Rc::downgrade(&child.0));
+        }
+        child
+    }
+
+    /// Cancel the token when the guard is dropped, unless it is disarmed first.
+    #[doc(hidden)]
+    pub fn drop_guard(self) -> DropGuard {
+        DropGuard(Some(self))
+    }
+
+    fn on_cancel(&self, callback: Box<dyn FnOnce()>) {
+        if self.is_cancelled() {
+            callback()
+        } else {
+            self.0.callbacks.borrow_mut().push(callback)
+        }
+    }
+}
+
+impl Inner {
+    fn cancel(&self) {
+        if self.cancelled.replace(true) {
+            return;
+        }
+        let callbacks = std::mem::take(&mut *self.callbacks.borrow_mut());
+        for callback in callbacks {
+            callback()
+        }
+        let children = std::mem::take(&mut *self.children.borrow_mut());
+        for child in children.iter().filter_map(Weak::upgrade) {
+            child.cancel()
+        }
+    }
+}
+
+impl fmt::Debug for CancellationToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CancellationToken")
+            .field("cancelled", &self.is_cancelled())
+            .finish()
+    }
+}
+
+#[doc(hidden)]
+#[derive(Debug)]
+pub struct DropGuard(Option<CancellationToken>);
+
+impl DropGuard {
+    pub fn disarm(mut self) -> CancellationToken {
+        self.0.take().expect("should not be disarmed yet")
+    }
+}
+
+impl Drop for DropGuard {
+    fn drop(&mut self) {
+        if let Some(token) = self.0.take() {
+            token.cancel()
+        }
+    }
+}
+
+/// Create an `AbortSignal` that is aborted when `token` is cancelled.
+///
+/// Requires `AbortController` to be defined in the global scope, for example by
+/// [`deno_web`](https://docs.rs/deno_web).
+#[doc(hidden)]
+pub fn abort_signal<'s>(
+    scope: &mut v8::HandleScope<'s>,
+    token: &CancellationToken,
+) -> anyhow::Result<v8::Local<'s, v8::Value>> {
+    let controller = {
+        let global = scope.get_current_context().global(scope);
+        let name = v8::String::new(scope, "AbortController").context("failed to create string")?;
+        global
+            .get(scope, name.into())
+            .and_then(|ctor| ctor.try_cast::<v8::Function>().ok())
+            .context("AbortController is not defined in the global scope")?
+            .new_instance(scope, &[])
+            .context("failed to create an AbortController")?
+    };
+
+    let signal = {
+        let name = v8::String::new(scope, "signal").context("failed to create string")?;
+        controller
+            .get(scope, name.into())
+            .context("failed to get AbortController.signal")?
+    };
+
+    if token.is_cancelled() {
+        abort(scope, controller)?;
+    } else {
+        let controller = v8::Global::new(scope, controller);
+        let spawner = {
+            let state = JsRuntime::op_state_from(scope);
+            let state = state.borrow();
+            state.borrow::<V8TaskSpawner>().clone()
+        };
+        token.on_cancel(Box::new(move || {
+            spawner.spawn(move |scope| {
+                let controller = v8::Local::new(scope, controller);
+                abort(scope, controller).ok();
+            })
+        }));
+    }
+
+    Ok(signal)
+}
+
+fn abort(scope: &mut v8::HandleScope, controller: v8::Local<v8::Object>) -> anyhow::Result<()> {
+    let scope = &mut v8::TryCatch::new(scope);
+    let name = v8::String::new(scope, "abort").context("failed to create string")?;
+    let abort = controller
+        .get(scope, name.into())
+        .and_then(|func| func.try_cast::<v8::Function>().ok())
+        .context("AbortController.abort is not a function")?;
+    match abort.call(scope, controller.into(), &[]) {
+        Some(_) => Ok(()),
+        None => Err(anyhow!("AbortController.abort threw an exception")),
+    }
+}
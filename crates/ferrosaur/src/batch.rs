@@ -0,0 +1,127 @@
+use deno_core::{
+    anyhow::{self, anyhow, Context},
+    error::JsError,
+    v8, JsRuntime,
+};
+
+use crate::{Error, Result};
+
+/// Items pulled from a JavaScript iterator by [`next_batch`].
+#[doc(hidden)]
+pub struct Batch<'s> {
+    pub items: Vec<v8::Local<'s, v8::Value>>,
+    /// The iterator is exhausted, or threw an exception.
+    pub done: bool,
+    /// The exception thrown after `items` were pulled.
+    pub error: Option<Error>,
+}
+
+/// Pull up to `size` items from a JavaScript iterator in a single call into JavaScript.
+///
+/// Used by generated code for `js(iterator(batch = ...))`. Like `next()`, a final
+/// `{ done: true, value }` is produced as an item if `value` is not `undefined`.
+#[doc(hidden)]
+pub fn next_batch<'s>(
+    scope: &mut v8::HandleScope<'s>,
+    iterator: v8::Local<'s, v8::Value>,
+    size: usize,
+) -> Result<Batch<'s>> {
+    let helper = helper(scope).map_err(Error::other)?;
+
+    let size = u32::try_from(size).unwrap_or(u32::MAX);
+    let size = v8::Integer::new_from_unsigned(scope, size).into();
+    let recv = v8::undefined(scope).into();
+
+    let result = {
+        let scope = &mut v8::TryCatch::new(scope);
+        match helper.call(scope, recv, &[iterator, size]) {
+            Some(result) => result,
+            None => {
+                return Err(match scope.exception() {
+                    Some(exception) => JsError::from_v8_exception(scope, exception).into(),
+                    None => Error::other(anyhow!("failed to pull items from iterator")),
+                })
+            }
+        }
+    };
+
+    let batch = (|| {
+        let result = result
+            .try_cast::<v8::Array>()
+            .ok()
+            .context("expected an array")?;
+
+        let items = result
+            .get_index(scope, 0)
+            .and_then(|items| items.try_cast::<v8::Array>().ok())
+            .context("expected an array of items")?;
+
+        let items = (0..items.length())
+            .map(|index| items.get_index(scope, index).context("missing item"))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        let done = result
+            .get_index(scope, 1)
+            .is_some_and(|done| done.is_true());
+
+        let error = if result
+            .get_index(scope, 2)
+            .is_some_and(|threw| threw.is_true())
+        {
+            let exception = result
+                .get_index(scope, 3)
+                .unwrap_or_else(|| v8::undefined(scope).into());
+            Some(JsError::from_v8_exception(scope, exception).into())
+        } else {
+            None
+        };
+
+        anyhow::Ok(Batch { items, done, error })
+    })();
+
+    batch
+        .context("unexpected result from iterator batch")
+        .map_err(Error::other)
+}
+
+struct BatchHelper(v8::Global<v8::Function>);
+
+const BATCH_HELPER: &str = r#"(iterator, size) => {
+  const items = [];
+  try {
+    while (items.length < size) {
+      const { done, value } = iterator.next();
+      if (done) {
+        if (value !== undefined) {
+          items.push(value);
+        }
+        return [items, true, false];
+      }
+      items.push(value);
+    }
+  } catch (error) {
+    return [items, true, true, error];
+  }
+  return [items, false, false];
+}"#;
+
+/// Compile the JavaScript side of [`next_batch`] once per runtime.
+fn helper<'s>(scope: &mut v8::HandleScope<'s>) -> anyhow::Result<v8::Local<'s, v8::Function>> {
+    let state = JsRuntime::op_state_from(scope);
+
+    if let Some(BatchHelper(helper)) = state.borrow().try_borrow::<BatchHelper>() {
+        return Ok(v8::Local::new(scope, helper));
+    }
+
+    let source = v8::String::new(scope, BATCH_HELPER).context("failed to create a v8::String")?;
+
+    let helper = v8::Script::compile(scope, source, None)
+        .and_then(|script| script.run(scope))
+        .and_then(|helper| helper.try_cast::<v8::Function>().ok())
+        .context("failed to compile iterator batch helper")?;
+
+    let global = v8::Global::new(scope, helper);
+    state.borrow_mut().put(BatchHelper(global));
+
+    Ok(helper)
+}
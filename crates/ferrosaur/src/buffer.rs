@@ -0,0 +1,53 @@
+use std::mem::size_of_val;
+
+use deno_core::v8;
+
+/// Numbers that can be the elements of a typed array.
+///
+/// # Safety
+///
+/// The type must have no padding, and every bit pattern must be a valid value.
+#[doc(hidden)]
+pub unsafe trait TypedArrayElement: Copy + Default + 'static {}
+
+macro_rules! typed_array_element {
+    ($($ty:ty),*) => {
+        $(unsafe impl TypedArrayElement for $ty {})*
+    };
+}
+
+typed_array_element!(u8, i8, u16, i16, u32, i32, u64, i64, f32, f64);
+
+/// Move `data` into a new `ArrayBuffer` without copying.
+#[doc(hidden)]
+pub fn new_array_buffer<'s, T: TypedArrayElement>(
+    scope: &mut v8::HandleScope<'s>,
+    data: Vec<T>,
+) -> v8::Local<'s, v8::ArrayBuffer> {
+    let store = v8::ArrayBuffer::new_backing_store_from_bytes(Box::new(Owned(data)));
+    v8::ArrayBuffer::with_backing_store(scope, &store.make_shared())
+}
+
+/// Copy the contents of `view` into a new `Vec`.
+#[doc(hidden)]
+pub fn copy_from_view<T: TypedArrayElement>(view: v8::Local<v8::ArrayBufferView>) -> Vec<T> {
+    let mut data = vec![T::default(); view.byte_length() / size_of::<T>()];
+    view.copy_contents(as_bytes_mut(&mut data));
+    data
+}
+
+/// Owns the memory of an `ArrayBuffer` created by [`new_array_buffer`].
+struct Owned<T>(Vec<T>);
+
+impl<T: TypedArrayElement> AsMut<[u8]> for Owned<T> {
+    fn as_mut(&mut self) -> &mut [u8] {
+        as_bytes_mut(&mut self.0)
+    }
+}
+
+fn as_bytes_mut<T: TypedArrayElement>(data: &mut [T]) -> &mut [u8] {
+    let len = size_of_val(data);
+    // SAFETY: TypedArrayElement guarantees that T has no padding and that any bytes
+    // written through the returned slice form valid values of T.
+    unsafe { std::slice::from_raw_parts_mut(data.as_mut_ptr().cast(), len) }
+}
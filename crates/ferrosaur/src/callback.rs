@@ -0,0 +1,89 @@
+use std::{
+    cell::Cell,
+    panic::{catch_unwind, AssertUnwindSafe},
+    rc::Rc,
+};
+
+use deno_core::{
+    anyhow::{self, anyhow},
+    v8,
+};
+
+/// A Rust function that can be called from JavaScript.
+///
+/// Implemented by code generated for `impl Fn(...)` arguments.
+#[doc(hidden)]
+pub trait Callback: 'static {
+    fn call<'s>(
+        &self,
+        scope: &mut v8::HandleScope<'s>,
+        args: &v8::FunctionCallbackArguments<'s>,
+    ) -> anyhow::Result<v8::Local<'s, v8::Value>>;
+}
+
+/// Create a JavaScript function that calls into `callback`.
+///
+/// `callback` is dropped once the function is garbage collected, or when the runtime is
+/// dropped, whichever comes first.
+#[doc(hidden)]
+pub fn new_callback<'s, C: Callback>(
+    scope: &mut v8::HandleScope<'s>,
+    callback: C,
+) -> anyhow::Result<v8::Local<'s, v8::Function>> {
+    let data = Box::into_raw(Box::new(callback));
+
+    let external = v8::External::new(scope, data.cast());
+
+    let Some(func) = v8::Function::builder(trampoline::<C>)
+        .data(external.into())
+        .build(scope)
+    else {
+        drop(unsafe { Box::from_raw(data) });
+        return Err(anyhow!("failed to create a v8::Function"));
+    };
+
+    // dropping the handle before the finalizer runs would uninstall the finalizer, so the
+    // finalizer owns the handle, and frees it together with the callback
+    let handle = Rc::new(Cell::new(None::<v8::Weak<v8::Function>>));
+
+    let finalize = Box::new({
+        let handle = handle.clone();
+        move || {
+            drop(handle.take());
+            drop(unsafe { Box::from_raw(data) });
+        }
+    });
+
+    handle.set(Some(v8::Weak::with_guaranteed_finalizer(
+        scope, func, finalize,
+    )));
+
+    Ok(func)
+}
+
+fn trampoline<'s, C: Callback>(
+    scope: &mut v8::HandleScope<'s>,
+    args: v8::FunctionCallbackArguments<'s>,
+    mut rv: v8::ReturnValue,
+) {
+    let Ok(data) = args.data().try_cast::<v8::External>() else {
+        return throw(scope, "callback data is missing");
+    };
+
+    // SAFETY: the pointer stays valid until the function is garbage collected
+    let callback = unsafe { &*data.value().cast::<C>() };
+
+    match catch_unwind(AssertUnwindSafe(|| callback.call(scope, &args))) {
+        Ok(Ok(value)) => rv.set(value),
+        Ok(Err(err)) => throw(scope, &format!("{err:#}")),
+        Err(_) => throw(scope, "callback panicked"),
+    }
+}
+
+fn throw(scope: &mut v8::HandleScope, message: &str) {
+    let Some(message) = v8::String::new(scope, message) else {
+        return;
+    };
+    let exception = v8::Exception::error(scope, message);
+    scope.throw_exception(exception);
+}
@@ -0,0 +1,244 @@
+use std::{
+    any::TypeId,
+    cell::{Ref, RefCell, RefMut},
+    collections::HashMap,
+    panic::{catch_unwind, AssertUnwindSafe},
+};
+
+use deno_core::{
+    anyhow::{self, anyhow, Context},
+    cppgc::{try_unwrap_cppgc_object, wrap_object, GarbageCollected, Ptr},
+    v8, JsRuntime,
+};
+
+use crate::{Error, Result};
+
+/// A Rust type that JavaScript can construct and call methods on.
+///
+/// Implemented by `#[js(class)]`. The class body is defined by `#[js(export)]`.
+pub trait JsClass: Sized + 'static {
+    /// The name of the class in JavaScript.
+    const NAME: &'static str;
+
+    /// Get the class constructor for this runtime.
+    ///
+    /// The constructor is created once per runtime, so `instanceof` works across calls.
+    fn constructor(rt: &mut JsRuntime) -> Result<v8::Global<v8::Function>>
+    where
+        Self: ExportClass,
+    {
+        let scope = &mut rt.handle_scope();
+        let func = class_constructor::<Self>(scope).map_err(Error::other)?;
+        Ok(v8::Global::new(scope, func))
+    }
+
+    /// Define the class constructor as a property of `globalThis`, named [`Self::NAME`].
+    fn register(rt: &mut JsRuntime) -> Result<()>
+    where
+        Self: ExportClass,
+    {
+        let scope = &mut rt.handle_scope();
+        (|| {
+            let func = class_constructor::<Self>(scope)?;
+            let name =
+                v8::String::new(scope, Self::NAME).context("failed to create a v8::String")?;
+            let global = scope.get_current_context().global(scope);
+            global
+                .set(scope, name.into(), func.into())
+                .with_context(|| format!("failed to define class {}", Self::NAME))?;
+            anyhow::Ok(())
+        })()
+        .map_err(Error::other)
+    }
+}
+
+/// The members of a [`JsClass`].
+///
+/// Implemented by `#[js(export)]`.
+#[doc(hidden)]
+pub trait ExportClass: JsClass {
+    /// Create a new value from arguments passed to `new`.
+    fn construct<'s>(
+        scope: &mut v8::HandleScope<'s>,
+        args: &v8::FunctionCallbackArguments<'s>,
+    ) -> anyhow::Result<Self>;
+
+    /// Define methods on the class template, using [`define_method`].
+    fn define<'s>(
+        scope: &mut v8::HandleScope<'s>,
+        class: v8::Local<'s, v8::FunctionTemplate>,
+    ) -> anyhow::Result<()>;
+}
+
+/// A method of a [`JsClass`], called with the arguments JavaScript passed.
+#[doc(hidden)]
+pub trait Method: 'static {
+    fn call<'s>(
+        scope: &mut v8::HandleScope<'s>,
+        args: &v8::FunctionCallbackArguments<'s>,
+    ) -> anyhow::Result<v8::Local<'s, v8::Value>>;
+}
+
+/// Where a [`Method`] is defined.
+#[doc(hidden)]
+#[derive(Debug, Clone, Copy)]
+pub enum MethodKind {
+    /// On the prototype, called on instances.
+    Instance,
+    /// On the constructor itself.
+    Static,
+}
+
+/// The Rust value owned by an instance of a [`JsClass`].
+///
+/// The value is dropped when the instance is garbage collected.
+#[doc(hidden)]
+pub struct Instance<T>(RefCell<T>);
+
+impl<T> GarbageCollected for Instance<T> {}
+
+impl<T: JsClass> Instance<T> {
+    pub fn borrow(&self) -> anyhow::Result<Ref<'_, T>> {
+        self.0
+            .try_borrow()
+            .map_err(|_| anyhow!("{} is already mutably borrowed", T::NAME))
+    }
+
+    pub fn borrow_mut(&self) -> anyhow::Result<RefMut<'_, T>> {
+        self.0
+            .try_borrow_mut()
+            .map_err(|_| anyhow!("{} is already borrowed", T::NAME))
+    }
+}
+
+/// Get the Rust value owned by `this`.
+#[doc(hidden)]
+pub fn unwrap_instance<T: JsClass>(
+    scope: &mut v8::HandleScope<'_>,
+    this: v8::Local<'_, v8::Object>,
+) -> anyhow::Result<Ptr<Instance<T>>> {
+    try_unwrap_cppgc_object::<Instance<T>>(scope, this.into())
+        .with_context(|| format!("`this` is not an instance of {}", T::NAME))
+}
+
+/// Create an instance of a class from a Rust value, without calling its constructor.
+#[doc(hidden)]
+pub fn new_instance<'s, T: ExportClass>(
+    scope: &mut v8::HandleScope<'s>,
+    value: T,
+) -> anyhow::Result<v8::Local<'s, v8::Object>> {
+    let class = template::<T>(scope)?;
+    let object = class
+        .instance_template(scope)
+        .new_instance(scope)
+        .with_context(|| format!("failed to create an instance of {}", T::NAME))?;
+    Ok(wrap_object(scope, object, Instance(RefCell::new(value))))
+}
+
+/// Define `M` as a method named `name`.
+#[doc(hidden)]
+pub fn define_method<'s, M: Method>(
+    scope: &mut v8::HandleScope<'s>,
+    class: v8::Local<'s, v8::FunctionTemplate>,
+    name: &str,
+    kind: MethodKind,
+) -> anyhow::Result<()> {
+    let name = v8::String::new(scope, name).context("failed to create a v8::String")?;
+    let method = v8::FunctionTemplate::new(scope, trampoline::<M>);
+    method.set_class_name(name);
+    match kind {
+        MethodKind::Instance => class
+            .prototype_template(scope)
+            .set(name.into(), method.into()),
+        MethodKind::Static => class.set(name.into(), method.into()),
+    }
+    Ok(())
+}
+
+struct ClassTemplates(HashMap<TypeId, v8::Global<v8::FunctionTemplate>>);
+
+/// Get the class constructor of `T` for this runtime.
+#[doc(hidden)]
+pub fn class_constructor<'s, T: ExportClass>(
+    scope: &mut v8::HandleScope<'s>,
+) -> anyhow::Result<v8::Local<'s, v8::Function>> {
+    template::<T>(scope)?
+        .get_function(scope)
+        .with_context(|| format!("failed to create class {}", T::NAME))
+}
+
+/// Create the template for `T` once per runtime.
+fn template<'s, T: ExportClass>(
+    scope: &mut v8::HandleScope<'s>,
+) -> anyhow::Result<v8::Local<'s, v8::FunctionTemplate>> {
+    let state = JsRuntime::op_state_from(scope);
+
+    if let Some(ClassTemplates(templates)) = state.borrow().try_borrow::<ClassTemplates>() {
+        if let Some(class) = templates.get(&TypeId::of::<T>()) {
+            return Ok(v8::Local::new(scope, class));
+        }
+    }
+
+    let class = v8::FunctionTemplate::new(scope, construct::<T>);
+    let name = v8::String::new(scope, T::NAME).context("failed to create a v8::String")?;
+    class.set_class_name(name);
+    T::define(scope, class)?;
+
+    let global = v8::Global::new(scope, class);
+    let mut state = state.borrow_mut();
+    if !state.has::<ClassTemplates>() {
+        state.put(ClassTemplates(HashMap::new()));
+    }
+    state
+        .borrow_mut::<ClassTemplates>()
+        .0
+        .insert(TypeId::of::<T>(), global);
+
+    Ok(class)
+}
+
+fn construct<'s, T: ExportClass>(
+    scope: &mut v8::HandleScope<'s>,
+    args: v8::FunctionCallbackArguments<'s>,
+    _: v8::ReturnValue,
+) {
+    if args.new_target().is_undefined() {
+        let message = format!(
+            "Class constructor {} cannot be invoked without 'new'",
+            T::NAME
+        );
+        return throw(scope, &message, v8::Exception::type_error);
+    }
+
+    match catch_unwind(AssertUnwindSafe(|| T::construct(scope, &args))) {
+        Ok(Ok(value)) => {
+            wrap_object(scope, args.this(), Instance(RefCell::new(value)));
+        }
+        Ok(Err(err)) => throw(scope, &format!("{err:#}"), v8::Exception::error),
+        Err(_) => throw(scope, "constructor panicked", v8::Exception::error),
+    }
+}
+
+fn trampoline<'s, M: Method>(
+    scope: &mut v8::HandleScope<'s>,
+    args: v8::FunctionCallbackArguments<'s>,
+    mut rv: v8::ReturnValue,
+) {
+    match catch_unwind(AssertUnwindSafe(|| M::call(scope, &args))) {
+        Ok(Ok(value)) => rv.set(value),
+        Ok(Err(err)) => throw(scope, &format!("{err:#}"), v8::Exception::error),
+        Err(_) => throw(scope, "method panicked", v8::Exception::error),
+    }
+}
+
+fn throw<'s>(
+    scope: &mut v8::HandleScope<'s>,
+    message: &str,
+    exception: fn(&mut v8::HandleScope<'s>, v8::Local<'s, v8::String>) -> v8::Local<'s, v8::Value>,
+) {
+    let Some(message) = v8::String::new(scope, message) else {
+        return;
+    };
+    let exception = exception(scope, message);
+    scope.throw_exception(exception);
+}
@@ -0,0 +1,7 @@
+//! Conversions between Rust and JavaScript builtin objects.
+
+mod date;
+mod error;
+mod regexp;
+
+pub use self::{date::DateLike, error::ErrorObject, regexp::RegExp};
@@ -0,0 +1,67 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Types that can be converted to and from a JavaScript `Date`, for use with `date<T>`.
+///
+/// Timestamps are represented as milliseconds since the Unix epoch, which is how `Date`
+/// stores time. Returning [`None`] means the timestamp is out of range.
+pub trait DateLike: Sized {
+    fn to_millis(&self) -> Option<f64>;
+
+    fn from_millis(millis: f64) -> Option<Self>;
+}
+
+/// The maximum absolute value of a `Date`, in milliseconds.
+///
+/// See <https://tc39.es/ecma262/#sec-time-values-and-time-range>.
+const MAX_MILLIS: f64 = 8.64e15;
+
+fn in_range(millis: f64) -> Option<f64> {
+    if millis.abs() <= MAX_MILLIS {
+        Some(millis)
+    } else {
+        None
+    }
+}
+
+impl DateLike for SystemTime {
+    fn to_millis(&self) -> Option<f64> {
+        let millis = match self.duration_since(UNIX_EPOCH) {
+            Ok(after) => after.as_millis() as f64,
+            Err(before) => -(before.duration().as_millis() as f64),
+        };
+        in_range(millis)
+    }
+
+    fn from_millis(millis: f64) -> Option<Self> {
+        let millis = in_range(millis)?;
+        let duration = Duration::from_millis(millis.abs() as u64);
+        if millis >= 0.0 {
+            UNIX_EPOCH.checked_add(duration)
+        } else {
+            UNIX_EPOCH.checked_sub(duration)
+        }
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl DateLike for chrono::DateTime<chrono::Utc> {
+    fn to_millis(&self) -> Option<f64> {
+        in_range(self.timestamp_millis() as f64)
+    }
+
+    fn from_millis(millis: f64) -> Option<Self> {
+        Self::from_timestamp_millis(in_range(millis)? as i64)
+    }
+}
+
+#[cfg(feature = "time")]
+impl DateLike for time::OffsetDateTime {
+    fn to_millis(&self) -> Option<f64> {
+        in_range((self.unix_timestamp_nanos() / 1_000_000) as f64)
+    }
+
+    fn from_millis(millis: f64) -> Option<Self> {
+        let nanos = in_range(millis)? as i128 * 1_000_000;
+        Self::from_unix_timestamp_nanos(nanos).ok()
+    }
+}
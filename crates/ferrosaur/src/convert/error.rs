@@ -0,0 +1,185 @@
+use std::fmt;
+
+use deno_core::{
+    anyhow::{self, anyhow},
+    convert::{FromV8, ToV8},
+    v8,
+};
+
+use crate::Error;
+
+/// A JavaScript `Error`, represented by its `name`, `message`, `stack` and `cause`.
+///
+/// Use [`ErrorObject::from_error`] to pass a Rust error (including its
+/// [`source`][std::error::Error::source] chain) to JavaScript as an `Error`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ErrorObject {
+    pub name: String,
+    pub message: String,
+    pub stack: Option<String>,
+    pub cause: Option<Box<ErrorObject>>,
+}
+
+impl ErrorObject {
+    pub fn new<S: Into<String>>(message: S) -> Self {
+        Self {
+            name: "Error".into(),
+            message: message.into(),
+            stack: None,
+            cause: None,
+        }
+    }
+
+    pub fn with_name<S: Into<String>>(self, name: S) -> Self {
+        Self {
+            name: name.into(),
+            ..self
+        }
+    }
+
+    pub fn with_cause(self, cause: ErrorObject) -> Self {
+        Self {
+            cause: Some(Box::new(cause)),
+            ..self
+        }
+    }
+
+    pub fn from_error(error: &(dyn std::error::Error + 'static)) -> Self {
+        let this = Self::new(error.to_string());
+        match error.source() {
+            Some(source) => this.with_cause(Self::from_error(source)),
+            None => this,
+        }
+    }
+}
+
+impl fmt::Display for ErrorObject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.name.as_str(), self.message.as_str()) {
+            (name, "") => write!(f, "{name}"),
+            ("", message) => write!(f, "{message}"),
+            (name, message) => write!(f, "{name}: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for ErrorObject {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.cause.as_deref().map(|cause| cause as _)
+    }
+}
+
+impl From<anyhow::Error> for ErrorObject {
+    fn from(error: anyhow::Error) -> Self {
+        Self::from_error(error.as_ref())
+    }
+}
+
+impl<'a> ToV8<'a> for ErrorObject {
+    type Error = Error;
+
+    fn to_v8(
+        self,
+        scope: &mut v8::HandleScope<'a>,
+    ) -> Result<v8::Local<'a, v8::Value>, Self::Error> {
+        let message = new_string(scope, &self.message)?;
+        let error = match self.name.as_str() {
+            "Error" => v8::Exception::error(scope, message),
+            "RangeError" => v8::Exception::range_error(scope, message),
+            "ReferenceError" => v8::Exception::reference_error(scope, message),
+            "SyntaxError" => v8::Exception::syntax_error(scope, message),
+            "TypeError" => v8::Exception::type_error(scope, message),
+            name => {
+                let error = v8::Exception::error(scope, message);
+                let name = new_string(scope, name)?;
+                set(scope, error, "name", name.into())?;
+                error
+            }
+        };
+        if let Some(stack) = self.stack {
+            let stack = new_string(scope, &stack)?;
+            set(scope, error, "stack", stack.into())?;
+        }
+        if let Some(cause) = self.cause {
+            let cause = cause.to_v8(scope)?;
+            set(scope, error, "cause", cause)?;
+        }
+        Ok(error)
+    }
+}
+
+impl<'a> FromV8<'a> for ErrorObject {
+    type Error = Error;
+
+    fn from_v8(
+        scope: &mut v8::HandleScope<'a>,
+        value: v8::Local<'a, v8::Value>,
+    ) -> Result<Self, Self::Error> {
+        Ok(from_value(scope, value, MAX_CAUSES))
+    }
+}
+
+/// Guards against cyclic `cause`s.
+const MAX_CAUSES: usize = 32;
+
+fn from_value(
+    scope: &mut v8::HandleScope,
+    value: v8::Local<v8::Value>,
+    depth: usize,
+) -> ErrorObject {
+    let Ok(object) = value.try_cast::<v8::Object>() else {
+        return ErrorObject::new(value.to_rust_string_lossy(scope));
+    };
+    let name = get(scope, object, "name")
+        .filter(|name| name.is_string())
+        .map(|name| name.to_rust_string_lossy(scope))
+        .unwrap_or_else(|| "Error".into());
+    let message = get(scope, object, "message")
+        .filter(|message| !message.is_null_or_undefined())
+        .map(|message| message.to_rust_string_lossy(scope))
+        .unwrap_or_default();
+    let stack = get(scope, object, "stack")
+        .filter(|stack| stack.is_string())
+        .map(|stack| stack.to_rust_string_lossy(scope));
+    let cause = get(scope, object, "cause")
+        .filter(|cause| !cause.is_undefined())
+        .filter(|_| depth > 0)
+        .map(|cause| Box::new(from_value(scope, cause, depth - 1)));
+    ErrorObject {
+        name,
+        message,
+        stack,
+        cause,
+    }
+}
+
+fn new_string<'a>(
+    scope: &mut v8::HandleScope<'a>,
+    value: &str,
+) -> Result<v8::Local<'a, v8::String>, Error> {
+    v8::String::new(scope, value)
+        .ok_or_else(|| Error::other(anyhow!("failed to create a v8::String")))
+}
+
+fn get<'a>(
+    scope: &mut v8::HandleScope<'a>,
+    object: v8::Local<v8::Object>,
+    key: &'static str,
+) -> Option<v8::Local<'a, v8::Value>> {
+    let key = v8::String::new_external_onebyte_static(scope, key.as_bytes())?;
+    object.get(scope, key.into())
+}
+
+fn set(
+    scope: &mut v8::HandleScope,
+    error: v8::Local<v8::Value>,
+    key: &'static str,
+    value: v8::Local<v8::Value>,
+) -> Result<(), Error> {
+    let error = error.try_cast::<v8::Object>().map_err(Error::other)?;
+    let name = new_string(scope, key)?;
+    error
+        .set(scope, name.into(), value)
+        .ok_or_else(|| Error::other(anyhow!("failed to set Error.{key}")))?;
+    Ok(())
+}
@@ -0,0 +1,72 @@
+use deno_core::{
+    anyhow::anyhow,
+    convert::{FromV8, ToV8},
+    v8,
+};
+
+use crate::Error;
+
+/// A JavaScript `RegExp`, represented by its source and flags.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RegExp {
+    pub source: String,
+    pub flags: String,
+}
+
+impl RegExp {
+    pub fn new<S: Into<String>, F: Into<String>>(source: S, flags: F) -> Self {
+        Self {
+            source: source.into(),
+            flags: flags.into(),
+        }
+    }
+}
+
+impl<'a> ToV8<'a> for RegExp {
+    type Error = Error;
+
+    fn to_v8(
+        self,
+        scope: &mut v8::HandleScope<'a>,
+    ) -> Result<v8::Local<'a, v8::Value>, Self::Error> {
+        let mut flags = v8::RegExpCreationFlags::empty();
+        for flag in self.flags.chars() {
+            flags |= match flag {
+                'd' => v8::RegExpCreationFlags::HAS_INDICES,
+                'g' => v8::RegExpCreationFlags::GLOBAL,
+                'i' => v8::RegExpCreationFlags::IGNORE_CASE,
+                'm' => v8::RegExpCreationFlags::MULTILINE,
+                's' => v8::RegExpCreationFlags::DOT_ALL,
+                'u' => v8::RegExpCreationFlags::UNICODE,
+                'v' => v8::RegExpCreationFlags::UNICODE_SETS,
+                'y' => v8::RegExpCreationFlags::STICKY,
+                flag => return Err(Error::other(anyhow!("invalid RegExp flag {flag:?}"))),
+            }
+        }
+        let source = v8::String::new(scope, &self.source)
+            .ok_or_else(|| Error::other(anyhow!("failed to create a v8::String")))?;
+        let regexp = v8::RegExp::new(scope, source, flags).ok_or_else(|| {
+            Error::other(anyhow!("invalid RegExp /{}/{}", self.source, self.flags))
+        })?;
+        Ok(regexp.into())
+    }
+}
+
+impl<'a> FromV8<'a> for RegExp {
+    type Error = Error;
+
+    fn from_v8(
+        scope: &mut v8::HandleScope<'a>,
+        value: v8::Local<'a, v8::Value>,
+    ) -> Result<Self, Self::Error> {
+        let regexp = value.try_cast::<v8::RegExp>().map_err(Error::other)?;
+        let source = regexp.get_source(scope).to_rust_string_lossy(scope);
+        let key = v8::String::new_external_onebyte_static(scope, b"flags")
+            .ok_or_else(|| Error::other(anyhow!("failed to create a v8::String")))?;
+        let flags = regexp
+            .get(scope, key.into())
+            .ok_or_else(|| Error::other(anyhow!("failed to get RegExp flags")))?
+            .to_rust_string_lossy(scope);
+        Ok(Self { source, flags })
+    }
+}
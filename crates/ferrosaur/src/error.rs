@@ -0,0 +1,263 @@
+use std::{fmt, panic::Location, time::Duration};
+
+use deno_core::{anyhow, error::CoreError, error::JsError};
+
+/// Errors returned by functions, properties, and iterators generated by [`js`][crate::js].
+///
+/// Use [`kind`][Error::kind] to find out what went wrong, and [`call_site`][Error::call_site]
+/// to find out which binding failed and where it was called from.
+///
+/// The error converts into [`anyhow::Error`] with `?`. To inspect it from an
+/// [`anyhow::Error`], use [`downcast_ref`][anyhow::Error::downcast_ref]:
+///
+/// ```
+/// # use deno_core::anyhow;
+/// use ferrosaur::ErrorKind;
+///
+/// fn is_exception(error: &anyhow::Error) -> bool {
+///     matches!(
+///         error.downcast_ref::<ferrosaur::Error>().map(|e| e.kind()),
+///         Some(ErrorKind::Exception(_))
+///     )
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Error {
+    kind: ErrorKind,
+    site: Option<Box<CallSite>>,
+}
+
+/// The kind of failure described by an [`Error`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// JavaScript threw an exception, or an awaited promise was rejected.
+    Exception(Box<JsError>),
+    /// An argument could not be converted into a JavaScript value.
+    ///
+    /// `index` is the position of the argument in the Rust function signature, not
+    /// counting `self`.
+    ArgumentConversion {
+        index: usize,
+        name: &'static str,
+        source: anyhow::Error,
+    },
+    /// The returned value could not be converted from a JavaScript value.
+    ReturnConversion { source: anyhow::Error },
+    /// The value to be called or constructed is not a function.
+    ///
+    /// `name` is the property that was accessed, if any.
+    NotAFunction { name: Option<String> },
+    /// The receiver is not an object.
+    NotAnObject,
+    /// The runtime failed while driving the event loop.
+    Runtime(Box<CoreError>),
+    /// The call did not finish within its timeout, and JavaScript execution was terminated.
+    ///
+    /// See [`set_default_timeout`][crate::set_default_timeout].
+    Timeout { timeout: Duration },
+    /// Any other failure.
+    Other(anyhow::Error),
+}
+
+/// The binding that returned an [`Error`], and where in Rust it was called.
+#[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
+pub struct CallSite {
+    /// The Rust type the binding is declared on, as given by [`core::any::type_name`].
+    pub receiver: &'static str,
+    /// The name of the Rust function.
+    pub binding: &'static str,
+    /// The JavaScript property accessed on the receiver, if any, such as `toString` or
+    /// `[Symbol.iterator]`.
+    pub path: Option<&'static str>,
+    /// Where the binding was called.
+    ///
+    /// This is `None` for `async` functions, which cannot be `#[track_caller]`.
+    pub location: Option<&'static Location<'static>>,
+}
+
+/// [`Result`][core::result::Result] with [`Error`] as the default error type.
+pub type Result<T, E = Error> = core::result::Result<T, E>;
+
+impl Error {
+    /// What went wrong.
+    pub fn kind(&self) -> &ErrorKind {
+        &self.kind
+    }
+
+    /// Discard the call site and return what went wrong.
+    pub fn into_kind(self) -> ErrorKind {
+        self.kind
+    }
+
+    /// The binding that failed, if the error was returned by generated code.
+    pub fn call_site(&self) -> Option<&CallSite> {
+        self.site.as_deref()
+    }
+
+    /// The JavaScript exception, if this is an [`ErrorKind::Exception`].
+    pub fn as_exception(&self) -> Option<&JsError> {
+        match &self.kind {
+            ErrorKind::Exception(error) => Some(error),
+            _ => None,
+        }
+    }
+
+    /// The JavaScript stack trace of the exception, if there is one.
+    pub fn js_stack(&self) -> Option<&str> {
+        self.as_exception()?.stack.as_deref()
+    }
+
+    #[doc(hidden)]
+    pub fn argument<E: Into<anyhow::Error>>(index: usize, name: &'static str, error: E) -> Self {
+        ErrorKind::ArgumentConversion {
+            index,
+            name,
+            source: error.into(),
+        }
+        .into()
+    }
+
+    #[doc(hidden)]
+    pub fn output<E: Into<anyhow::Error>>(error: E) -> Self {
+        ErrorKind::ReturnConversion {
+            source: error.into(),
+        }
+        .into()
+    }
+
+    #[doc(hidden)]
+    pub fn other<E: Into<anyhow::Error>>(error: E) -> Self {
+        ErrorKind::Other(error.into()).into()
+    }
+
+    #[doc(hidden)]
+    pub fn timeout(timeout: Duration) -> Self {
+        ErrorKind::Timeout { timeout }.into()
+    }
+
+    #[doc(hidden)]
+    pub fn not_a_function(name: Option<&str>) -> Self {
+        ErrorKind::NotAFunction {
+            name: name.map(Into::into),
+        }
+        .into()
+    }
+
+    #[doc(hidden)]
+    pub fn not_an_object() -> Self {
+        ErrorKind::NotAnObject.into()
+    }
+
+    /// Attach the call site, unless the error already has one.
+    #[doc(hidden)]
+    pub fn at(mut self, site: CallSite) -> Self {
+        if self.site.is_none() {
+            self.site = Some(Box::new(site));
+        }
+        self
+    }
+}
+
+impl CallSite {
+    #[doc(hidden)]
+    pub fn new(
+        receiver: &'static str,
+        binding: &'static str,
+        path: Option<&'static str>,
+        location: Option<&'static Location<'static>>,
+    ) -> Self {
+        Self {
+            receiver,
+            binding,
+            path,
+            location,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.kind, f)?;
+        if let Some(site) = &self.site {
+            write!(f, "\n    in {site}")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Exception(error) => fmt::Display::fmt(error, f),
+            Self::ArgumentConversion { index, name, .. } => {
+                write!(f, "failed to convert argument {index} `{name}`")
+            }
+            Self::ReturnConversion { .. } => f.write_str("failed to convert returned value"),
+            Self::NotAFunction { name: Some(name) } => write!(f, "{name} is not a function"),
+            Self::NotAFunction { name: None } => f.write_str("value is not a function"),
+            Self::NotAnObject => f.write_str("value is not an object"),
+            Self::Runtime(error) => fmt::Display::fmt(error, f),
+            Self::Timeout { timeout } => {
+                write!(f, "execution timed out after {} ms", timeout.as_millis())
+            }
+            Self::Other(error) => fmt::Display::fmt(error, f),
+        }
+    }
+}
+
+impl fmt::Display for CallSite {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self {
+            receiver,
+            binding,
+            path,
+            location,
+        } = self;
+        write!(f, "{receiver}::{binding}")?;
+        if let Some(path) = path {
+            write!(f, " (property {path})")?;
+        }
+        if let Some(location) = location {
+            write!(f, ", called at {location}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match &self.kind {
+            ErrorKind::ArgumentConversion { source, .. }
+            | ErrorKind::ReturnConversion { source } => Some(source.as_ref()),
+            ErrorKind::Runtime(error) => error.source(),
+            ErrorKind::Other(error) => error.source(),
+            ErrorKind::Exception(_)
+            | ErrorKind::NotAFunction { .. }
+            | ErrorKind::NotAnObject
+            | ErrorKind::Timeout { .. } => None,
+        }
+    }
+}
+
+impl From<ErrorKind> for Error {
+    fn from(kind: ErrorKind) -> Self {
+        Self { kind, site: None }
+    }
+}
+
+impl From<JsError> for Error {
+    fn from(error: JsError) -> Self {
+        ErrorKind::Exception(Box::new(error)).into()
+    }
+}
+
+impl From<CoreError> for Error {
+    fn from(error: CoreError) -> Self {
+        match error {
+            CoreError::Js(error) => error.into(),
+            error => ErrorKind::Runtime(Box::new(error)).into(),
+        }
+    }
+}
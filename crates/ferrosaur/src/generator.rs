@@ -0,0 +1,11 @@
+/// The result of resuming a generator, returned by `resume` of `js(generator)` types.
+///
+/// Unlike iterators, generators can return a final value that is distinct from the values
+/// they yield.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GeneratorState<Y, R> {
+    /// The generator paused at a `yield` expression, producing a value.
+    Yielded(Y),
+    /// The generator finished, either by a `return` statement or by running to the end.
+    Complete(R),
+}
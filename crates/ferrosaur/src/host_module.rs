@@ -0,0 +1,67 @@
+use std::{borrow::Cow, collections::HashSet};
+
+use deno_core::{anyhow, v8, Extension, ExtensionFileSource, OpDecl, OpState};
+
+/// Create the extension of a module declared with `#[js(host_module)]`.
+///
+/// The module is the entry point of the extension. It takes the values of its exports from
+/// an array returned by `op`, in the same order as `names`. Extensions are evaluated before
+/// any other code runs, so other scripts cannot intercept the values.
+#[doc(hidden)]
+pub fn host_module_extension(specifier: &'static str, op: OpDecl, names: &[&str]) -> Extension {
+    let source = host_module_source(op.name, names);
+    Extension {
+        name: specifier,
+        esm_files: Cow::Owned(vec![ExtensionFileSource::new_computed(
+            specifier,
+            source.into(),
+        )]),
+        esm_entry_point: Some(specifier),
+        ops: Cow::Owned(vec![op]),
+        op_state_fn: Some(Box::new(move |state| register(state, specifier))),
+        ..Default::default()
+    }
+}
+
+/// Throw `error` from the op of a host module.
+#[doc(hidden)]
+pub fn throw_host_module_error<'s>(
+    scope: &mut v8::HandleScope<'s>,
+    error: anyhow::Error,
+) -> v8::Local<'s, v8::Value> {
+    if let Some(message) = v8::String::new(scope, &format!("{error:#}")) {
+        let exception = v8::Exception::error(scope, message);
+        scope.throw_exception(exception);
+    }
+    v8::undefined(scope).into()
+}
+
+fn host_module_source(op: &str, names: &[&str]) -> String {
+    let bindings = (0..names.len())
+        .map(|idx| format!("f{idx}"))
+        .collect::<Vec<_>>();
+    let imports = bindings.join(", ");
+    let exports = names
+        .iter()
+        .zip(bindings.iter())
+        .map(|(name, binding)| format!("{binding} as {name:?}"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!(
+        "const [{imports}] = Deno.core.ops.{op}();\n\
+        export {{ {exports} }};\n"
+    )
+}
+
+/// Specifiers of the host modules added to a runtime.
+struct HostModules(HashSet<&'static str>);
+
+fn register(state: &mut OpState, specifier: &'static str) {
+    if !state.has::<HostModules>() {
+        state.put(HostModules(HashSet::new()));
+    }
+    let HostModules(modules) = state.borrow_mut();
+    if !modules.insert(specifier) {
+        panic!("host module {specifier:?} was added to the runtime more than once");
+    }
+}
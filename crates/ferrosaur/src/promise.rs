@@ -0,0 +1,246 @@
+use std::{
+    fmt,
+    future::Future,
+    task::{Context as TaskContext, Poll},
+};
+
+use deno_core::{
+    anyhow::{self, anyhow, Context},
+    convert::ToV8,
+    futures::{
+        future::{select_all, try_join_all},
+        task::noop_waker_ref,
+        FutureExt,
+    },
+    unsync::spawn,
+    v8, JsRuntime, V8TaskSpawner,
+};
+
+use crate::{convert::ErrorObject, CancellationToken, Error, Result};
+
+/// A pending JavaScript promise, returned by functions declared as `fn ... -> JsPromise<T>`.
+///
+/// Unlike an `async fn`, calling the function does not wait for the promise to settle, so
+/// several calls can be started before any of them is awaited.
+///
+/// The promise is settled by driving the event loop, using [`await_in`][Self::await_in],
+/// [`all`][Self::all], or [`race`][Self::race]. Once it is fulfilled, the value is
+/// converted to `T` in the same way as the return value of an `async fn`.
+pub struct JsPromise<T> {
+    promise: v8::Global<v8::Value>,
+    convert: Convert<T>,
+    tokens: Vec<CancellationToken>,
+}
+
+type Convert<T> =
+    for<'s> fn(&mut v8::HandleScope<'s>, v8::Local<'s, v8::Value>) -> anyhow::Result<T>;
+
+impl<T> JsPromise<T> {
+    #[doc(hidden)]
+    pub fn new<'s>(
+        scope: &mut v8::HandleScope<'s>,
+        value: v8::Local<'s, v8::Value>,
+        convert: Convert<T>,
+    ) -> Self {
+        let promise = v8::Global::new(scope, value);
+        let tokens = vec![];
+        Self {
+            promise,
+            convert,
+            tokens,
+        }
+    }
+
+    /// Keep the `AbortSignal` passed to the call connected to `token` until the promise
+    /// is awaited.
+    #[doc(hidden)]
+    pub fn with_abort(mut self, token: CancellationToken) -> Self {
+        self.tokens.push(token);
+        self
+    }
+
+    /// Drive the event loop until the promise settles, and return its value.
+    ///
+    /// A rejected promise is returned as an [`ErrorKind::Exception`][crate::ErrorKind].
+    pub async fn await_in(self, rt: &mut JsRuntime) -> Result<T> {
+        let Self {
+            promise,
+            convert,
+            tokens: _tokens,
+        } = self;
+        let future = rt.resolve(promise);
+        let output = rt
+            .with_event_loop_promise(future, Default::default())
+            .await?;
+        let scope = &mut rt.handle_scope();
+        let output = v8::Local::new(scope, output);
+        convert(scope, output).map_err(Error::output)
+    }
+
+    /// Drive the event loop until all promises are fulfilled, or any of them is rejected.
+    ///
+    /// Values are returned in the same order as the promises, like `Promise.all()`.
+    pub async fn all<I>(rt: &mut JsRuntime, promises: I) -> Result<Vec<T>>
+    where
+        I: IntoIterator<Item = Self>,
+    {
+        let mut tokens = vec![];
+        let (futures, converts): (Vec<_>, Vec<_>) = promises
+            .into_iter()
+            .map(|promise| {
+                tokens.extend(promise.tokens);
+                (rt.resolve(promise.promise), promise.convert)
+            })
+            .unzip();
+        let outputs = rt
+            .with_event_loop_promise(try_join_all(futures), Default::default())
+            .await?;
+        let scope = &mut rt.handle_scope();
+        outputs
+            .into_iter()
+            .zip(converts)
+            .map(|(output, convert)| {
+                let output = v8::Local::new(scope, output);
+                convert(scope, output).map_err(Error::output)
+            })
+            .collect()
+    }
+
+    /// Drive the event loop until any of the promises settles, and return its outcome.
+    ///
+    /// Like `Promise.race()`, the first promise to be rejected causes an error even if
+    /// others would have been fulfilled. Returns an error if there are no promises.
+    pub async fn race<I>(rt: &mut JsRuntime, promises: I) -> Result<T>
+    where
+        I: IntoIterator<Item = Self>,
+    {
+        let mut tokens = vec![];
+        let (futures, converts): (Vec<_>, Vec<_>) = promises
+            .into_iter()
+            .map(|promise| {
+                tokens.extend(promise.tokens);
+                (Box::pin(rt.resolve(promise.promise)), promise.convert)
+            })
+            .unzip();
+        if futures.is_empty() {
+            return Err(Error::other(anyhow!(
+                "cannot race an empty list of promises"
+            )));
+        }
+        let race = async {
+            let (output, index, _) = select_all(futures).await;
+            output.map(|output| (output, index))
+        };
+        let (output, index) = rt
+            .with_event_loop_promise(Box::pin(race), Default::default())
+            .await?;
+        let scope = &mut rt.handle_scope();
+        let output = v8::Local::new(scope, output);
+        converts[index](scope, output).map_err(Error::output)
+    }
+
+    /// The current state of the promise, without driving the event loop.
+    ///
+    /// Values that are not promises are reported as [`Fulfilled`][v8::PromiseState].
+    pub fn state(&self, rt: &mut JsRuntime) -> v8::PromiseState {
+        let scope = &mut rt.handle_scope();
+        let value = v8::Local::new(scope, &self.promise);
+        match value.try_cast::<v8::Promise>() {
+            Ok(promise) => promise.state(),
+            Err(_) => v8::PromiseState::Fulfilled,
+        }
+    }
+}
+
+impl<T> fmt::Debug for JsPromise<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("JsPromise").finish_non_exhaustive()
+    }
+}
+
+/// Settle a promise by running pending microtasks, without polling the event loop.
+///
+/// Used by functions declared with `js(func(event_loop(none)))`. Promises that are still
+/// pending afterwards, such as those waiting on timers or ops, are reported as errors.
+#[doc(hidden)]
+pub fn resolve_without_event_loop(
+    rt: &mut JsRuntime,
+    promise: v8::Global<v8::Value>,
+) -> Result<v8::Global<v8::Value>> {
+    let mut future = rt.resolve(promise);
+    let cx = &mut TaskContext::from_waker(noop_waker_ref());
+    if let Poll::Ready(result) = future.poll_unpin(cx) {
+        return Ok(result?);
+    }
+    rt.handle_scope().perform_microtask_checkpoint();
+    match future.poll_unpin(cx) {
+        Poll::Ready(result) => Ok(result?),
+        Poll::Pending => Err(Error::other(anyhow!(
+            "promise is still pending, and event_loop(none) does not run the event loop"
+        ))),
+    }
+}
+
+/// Converts a value produced on the Rust side, such as the output of a future, into a
+/// JavaScript value.
+///
+/// Implemented by code generated for `promise<T>`, `js_iter<T>`, and `js_stream<T>` arguments.
+#[doc(hidden)]
+pub trait Resolve<T>: 'static {
+    fn resolve<'s>(
+        scope: &mut v8::HandleScope<'s>,
+        value: T,
+    ) -> anyhow::Result<v8::Local<'s, v8::Value>>;
+}
+
+/// Create a JavaScript promise that settles when `future` completes.
+///
+/// The future is spawned onto the current thread, and keeps the event loop alive until it
+/// completes. An error rejects the promise with an `Error` (see [`ErrorObject`]).
+#[doc(hidden)]
+pub fn new_promise<'s, R, T, F>(
+    scope: &mut v8::HandleScope<'s>,
+    future: F,
+) -> anyhow::Result<v8::Local<'s, v8::Promise>>
+where
+    R: Resolve<T>,
+    T: 'static,
+    F: Future<Output = anyhow::Result<T>> + 'static,
+{
+    let resolver = v8::PromiseResolver::new(scope).context("failed to create a v8::Promise")?;
+
+    let promise = resolver.get_promise(scope);
+
+    let resolver = v8::Global::new(scope, resolver);
+
+    let (spawner, tracker) = {
+        let state = JsRuntime::op_state_from(scope);
+        let state = state.borrow();
+        let spawner = state.borrow::<V8TaskSpawner>().clone();
+        let tracker = state.external_ops_tracker.clone();
+        (spawner, tracker)
+    };
+
+    tracker.ref_op();
+
+    spawn(async move {
+        let result = future.await;
+        spawner.spawn(move |scope| {
+            tracker.unref_op();
+            let scope = &mut v8::TryCatch::new(scope);
+            let resolver = v8::Local::new(scope, resolver);
+            match result.and_then(|value| R::resolve(scope, value)) {
+                Ok(value) => resolver.resolve(scope, value),
+                Err(error) => match ErrorObject::from(error).to_v8(scope) {
+                    Ok(error) => resolver.reject(scope, error),
+                    Err(_) => {
+                        let error = v8::undefined(scope).into();
+                        resolver.reject(scope, error)
+                    }
+                },
+            };
+        });
+    });
+
+    Ok(promise)
+}
@@ -0,0 +1,203 @@
+use std::{cell::RefCell, marker::PhantomData, pin::Pin, rc::Rc};
+
+use deno_core::{
+    anyhow::{self, Context},
+    futures::{lock::Mutex, Stream, StreamExt},
+    v8,
+};
+
+use crate::{
+    callback::{new_callback, Callback},
+    promise::{new_promise, Resolve},
+};
+
+/// Create a JavaScript iterator that lazily pulls items from `iter`.
+///
+/// The iterator is also iterable, so it can be used in `for...of`. `iter` is dropped once it
+/// is exhausted, or when `return()` is called, such as when JavaScript breaks out of a loop.
+#[doc(hidden)]
+pub fn new_iterator<'s, R, T, I>(
+    scope: &mut v8::HandleScope<'s>,
+    iter: I,
+) -> anyhow::Result<v8::Local<'s, v8::Object>>
+where
+    R: Resolve<T>,
+    I: Iterator<Item = T> + 'static,
+{
+    let iter = Rc::new(RefCell::new(Some(iter)));
+    let next = new_callback(scope, Next::<R, I>(iter.clone(), PhantomData))?;
+    let close = new_callback(scope, Close::<R, I>(iter, PhantomData))?;
+    let symbol = v8::Symbol::get_iterator(scope);
+    new_object(scope, next, close, symbol)
+}
+
+/// Create a JavaScript async iterator that lazily pulls items from `stream`.
+///
+/// Each call to `next()` returns a promise. Calls that are made before the previous promise
+/// settles wait for their turn, so items are produced in order.
+#[doc(hidden)]
+pub fn new_async_iterator<'s, R, T, S>(
+    scope: &mut v8::HandleScope<'s>,
+    stream: S,
+) -> anyhow::Result<v8::Local<'s, v8::Object>>
+where
+    R: Resolve<T>,
+    T: 'static,
+    S: Stream<Item = T> + 'static,
+{
+    let stream = Rc::new(Mutex::new(Some(Box::pin(stream))));
+    let next = new_callback(scope, NextAsync::<R, S>(stream.clone(), PhantomData))?;
+    let close = new_callback(scope, CloseAsync::<R, S>(stream, PhantomData))?;
+    let symbol = v8::Symbol::get_async_iterator(scope);
+    new_object(scope, next, close, symbol)
+}
+
+type SharedIter<I> = Rc<RefCell<Option<I>>>;
+
+type SharedStream<S> = Rc<Mutex<Option<Pin<Box<S>>>>>;
+
+struct Next<R, I>(SharedIter<I>, PhantomData<R>);
+
+struct Close<R, I>(SharedIter<I>, PhantomData<R>);
+
+struct NextAsync<R, S>(SharedStream<S>, PhantomData<R>);
+
+struct CloseAsync<R, S>(SharedStream<S>, PhantomData<R>);
+
+impl<R, T, I> Callback for Next<R, I>
+where
+    R: Resolve<T>,
+    I: Iterator<Item = T> + 'static,
+{
+    fn call<'s>(
+        &self,
+        scope: &mut v8::HandleScope<'s>,
+        _: &v8::FunctionCallbackArguments<'s>,
+    ) -> anyhow::Result<v8::Local<'s, v8::Value>> {
+        let item = self.0.borrow_mut().as_mut().and_then(Iterator::next);
+        if item.is_none() {
+            self.0.borrow_mut().take();
+        }
+        Step::<R>::resolve(scope, item)
+    }
+}
+
+impl<R, T, I> Callback for Close<R, I>
+where
+    R: Resolve<T>,
+    I: Iterator<Item = T> + 'static,
+{
+    fn call<'s>(
+        &self,
+        scope: &mut v8::HandleScope<'s>,
+        _: &v8::FunctionCallbackArguments<'s>,
+    ) -> anyhow::Result<v8::Local<'s, v8::Value>> {
+        self.0.borrow_mut().take();
+        Step::<R>::resolve(scope, None)
+    }
+}
+
+impl<R, T, S> Callback for NextAsync<R, S>
+where
+    R: Resolve<T>,
+    T: 'static,
+    S: Stream<Item = T> + 'static,
+{
+    fn call<'s>(
+        &self,
+        scope: &mut v8::HandleScope<'s>,
+        _: &v8::FunctionCallbackArguments<'s>,
+    ) -> anyhow::Result<v8::Local<'s, v8::Value>> {
+        let stream = self.0.clone();
+        let future = async move {
+            let mut stream = stream.lock().await;
+            let item = match stream.as_mut() {
+                Some(stream) => stream.next().await,
+                None => None,
+            };
+            if item.is_none() {
+                stream.take();
+            }
+            Ok(item)
+        };
+        new_promise::<Step<R>, _, _>(scope, future).map(Into::into)
+    }
+}
+
+impl<R, T, S> Callback for CloseAsync<R, S>
+where
+    R: Resolve<T>,
+    T: 'static,
+    S: Stream<Item = T> + 'static,
+{
+    fn call<'s>(
+        &self,
+        scope: &mut v8::HandleScope<'s>,
+        _: &v8::FunctionCallbackArguments<'s>,
+    ) -> anyhow::Result<v8::Local<'s, v8::Value>> {
+        let stream = self.0.clone();
+        let future = async move {
+            stream.lock().await.take();
+            Ok(None)
+        };
+        new_promise::<Step<R>, _, _>(scope, future).map(Into::into)
+    }
+}
+
+/// Converts an item into an iterator result, `{ done, value }`.
+struct Step<R>(PhantomData<R>);
+
+impl<R, T> Resolve<Option<T>> for Step<R>
+where
+    R: Resolve<T>,
+{
+    fn resolve<'s>(
+        scope: &mut v8::HandleScope<'s>,
+        value: Option<T>,
+    ) -> anyhow::Result<v8::Local<'s, v8::Value>> {
+        let value = value.map(|value| R::resolve(scope, value)).transpose()?;
+        let done = v8::Boolean::new(scope, value.is_none()).into();
+        let value = value.unwrap_or_else(|| v8::undefined(scope).into());
+        let result = v8::Object::new(scope);
+        set(scope, result, "done", done)?;
+        set(scope, result, "value", value)?;
+        Ok(result.into())
+    }
+}
+
+fn new_object<'s>(
+    scope: &mut v8::HandleScope<'s>,
+    next: v8::Local<'s, v8::Function>,
+    close: v8::Local<'s, v8::Function>,
+    symbol: v8::Local<'s, v8::Symbol>,
+) -> anyhow::Result<v8::Local<'s, v8::Object>> {
+    let object = v8::Object::new(scope);
+    set(scope, object, "next", next.into())?;
+    set(scope, object, "return", close.into())?;
+    let this = v8::Function::new(scope, return_this).context("failed to create a v8::Function")?;
+    object
+        .set(scope, symbol.into(), this.into())
+        .context("failed to set iterator symbol")?;
+    Ok(object)
+}
+
+fn return_this(
+    _: &mut v8::HandleScope,
+    args: v8::FunctionCallbackArguments,
+    mut rv: v8::ReturnValue,
+) {
+    rv.set(args.this().into())
+}
+
+fn set<'s>(
+    scope: &mut v8::HandleScope<'s>,
+    object: v8::Local<'s, v8::Object>,
+    key: &str,
+    value: v8::Local<'s, v8::Value>,
+) -> anyhow::Result<()> {
+    let key = v8::String::new(scope, key).context("failed to create a v8::String")?;
+    object
+        .set(scope, key.into(), value)
+        .with_context(|| format!("failed to set property {key:?}"))?;
+    Ok(())
+}
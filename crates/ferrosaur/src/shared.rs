@@ -0,0 +1,28 @@
+use std::{cell::RefCell, ops::DerefMut, rc::Rc};
+
+use deno_core::{
+    anyhow::{self, anyhow},
+    JsRuntime,
+};
+
+/// Access to the [`JsRuntime`] held by an iterator between items.
+///
+/// Implemented for `&mut JsRuntime`, used by `into_iter`, and for
+/// `Rc<RefCell<JsRuntime>>`, used by `into_shared_iter`.
+#[doc(hidden)]
+pub trait BorrowRuntime {
+    fn borrow_runtime(&mut self) -> anyhow::Result<impl DerefMut<Target = JsRuntime> + '_>;
+}
+
+impl BorrowRuntime for &mut JsRuntime {
+    fn borrow_runtime(&mut self) -> anyhow::Result<impl DerefMut<Target = JsRuntime> + '_> {
+        Ok(&mut **self)
+    }
+}
+
+impl BorrowRuntime for Rc<RefCell<JsRuntime>> {
+    fn borrow_runtime(&mut self) -> anyhow::Result<impl DerefMut<Target = JsRuntime> + '_> {
+        self.try_borrow_mut()
+            .map_err(|_| anyhow!("JsRuntime is already borrowed elsewhere"))
+    }
+}
@@ -0,0 +1,157 @@
+use std::fmt;
+
+use deno_core::{
+    anyhow::{self, Context},
+    error::JsError,
+    v8,
+};
+
+use crate::{error::CallSite, Error};
+
+/// Types that can be created from a thrown JavaScript value.
+///
+/// Implemented by enums declared with `#[js(error)]`, for use with `js(func(throws = ...))`.
+pub trait FromException: Sized {
+    /// Convert the thrown value, or return `Ok(None)` if it is not an expected error.
+    ///
+    /// If this returns an error, the exception is reported as [`JsThrown::Error`], as if
+    /// it were not expected.
+    fn from_exception<'a>(
+        scope: &mut v8::HandleScope<'a>,
+        exception: v8::Local<'a, v8::Value>,
+    ) -> anyhow::Result<Option<Self>>;
+}
+
+/// The error returned by functions declared with `js(func(throws = E))`.
+#[derive(Debug)]
+pub enum JsThrown<E> {
+    /// JavaScript threw one of the errors described by `E`.
+    ///
+    /// `site` is the binding that threw it, and where it was called.
+    Thrown {
+        error: E,
+        site: Option<Box<CallSite>>,
+    },
+    /// Any other failure, including exceptions that are not described by `E`.
+    Error(Error),
+}
+
+impl<E> JsThrown<E> {
+    /// The expected error, if JavaScript threw one.
+    pub fn thrown(&self) -> Option<&E> {
+        match self {
+            Self::Thrown { error, .. } => Some(error),
+            Self::Error(_) => None,
+        }
+    }
+
+    /// Take the expected error, or return the other failure.
+    pub fn into_thrown(self) -> Result<E, Error> {
+        match self {
+            Self::Thrown { error, .. } => Ok(error),
+            Self::Error(error) => Err(error),
+        }
+    }
+
+    /// The binding that failed, and where it was called.
+    pub fn call_site(&self) -> Option<&CallSite> {
+        match self {
+            Self::Thrown { site, .. } => site.as_deref(),
+            Self::Error(error) => error.call_site(),
+        }
+    }
+
+    #[doc(hidden)]
+    pub fn at(self, site: CallSite) -> Self {
+        match self {
+            Self::Thrown { error, site: None } => Self::Thrown {
+                error,
+                site: Some(Box::new(site)),
+            },
+            Self::Thrown { error, site } => Self::Thrown { error, site },
+            Self::Error(error) => Self::Error(error.at(site)),
+        }
+    }
+}
+
+impl<E: FromException> JsThrown<E> {
+    #[doc(hidden)]
+    pub fn from_exception<'a>(
+        scope: &mut v8::HandleScope<'a>,
+        exception: v8::Local<'a, v8::Value>,
+    ) -> Self {
+        match E::from_exception(scope, exception) {
+            Ok(Some(error)) => Self::Thrown { error, site: None },
+            Ok(None) | Err(_) => Self::Error(JsError::from_v8_exception(scope, exception).into()),
+        }
+    }
+}
+
+impl<E: fmt::Display> fmt::Display for JsThrown<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Thrown { error, .. } => fmt::Display::fmt(error, f),
+            Self::Error(error) => fmt::Display::fmt(error, f),
+        }
+    }
+}
+
+impl<E: std::error::Error> std::error::Error for JsThrown<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Thrown { error, .. } => error.source(),
+            Self::Error(error) => error.source(),
+        }
+    }
+}
+
+/// Guards against cyclic prototype chains.
+const MAX_PROTOTYPES: usize = 32;
+
+/// The names that `object` is matched by in [`FromException`]: its `name` property, then
+/// the name of each constructor in its prototype chain, starting from its own.
+#[doc(hidden)]
+pub fn exception_names(
+    scope: &mut v8::HandleScope,
+    object: v8::Local<v8::Object>,
+) -> anyhow::Result<Vec<String>> {
+    let name = v8::String::new_external_onebyte_static(scope, b"name")
+        .context("failed to create a v8::String")?;
+    let constructor = v8::String::new_external_onebyte_static(scope, b"constructor")
+        .context("failed to create a v8::String")?;
+
+    let mut names = vec![];
+
+    if let Some(name) = object.get(scope, name.into()) {
+        names.push(name.to_rust_string_lossy(scope));
+    }
+
+    let mut proto = object.get_prototype(scope);
+
+    for _ in 0..MAX_PROTOTYPES {
+        let Some(object) = proto.and_then(|proto| proto.try_cast::<v8::Object>().ok()) else {
+            break;
+        };
+        let ctor = object
+            .get(scope, constructor.into())
+            .and_then(|ctor| ctor.try_cast::<v8::Function>().ok());
+        if let Some(ctor) = ctor {
+            names.push(ctor.get_name(scope).to_rust_string_lossy(scope));
+        }
+        proto = object.get_prototype(scope);
+    }
+
+    Ok(names)
+}
+
+impl<E> From<Error> for JsThrown<E> {
+    fn from(error: Error) -> Self {
+        Self::Error(error)
+    }
+}
+
+impl<E> From<JsError> for JsThrown<E> {
+    fn from(error: JsError) -> Self {
+        Self::Error(error.into())
+    }
+}
@@ -0,0 +1,232 @@
+use std::{
+    future::Future,
+    mem,
+    pin::pin,
+    sync::{
+        mpsc::{self, RecvTimeoutError},
+        Arc, Mutex, MutexGuard, PoisonError,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use deno_core::{
+    futures::{
+        channel::oneshot,
+        future::{select, Either},
+    },
+    v8, JsRuntime,
+};
+
+use crate::Error;
+
+/// Set the timeout for calls made through generated bindings on this runtime.
+///
+/// Functions declared with `js(func(timeout_ms = ...))` use their own timeout instead.
+/// Pass `None` to remove the default.
+///
+/// When a call runs for longer than the timeout, JavaScript execution is terminated, and
+/// the call returns [`ErrorKind::Timeout`][crate::ErrorKind::Timeout]. The runtime can
+/// be used again afterwards.
+///
+/// The default only applies to function calls, such as `js(func)` and `js(new)`. Property
+/// accessors, indexers, and iterator methods are not covered.
+pub fn set_default_timeout(rt: &mut JsRuntime, timeout: Option<Duration>) {
+    let isolate = rt.v8_isolate();
+    match timeout {
+        Some(timeout) => drop(isolate.set_slot(DefaultTimeout(timeout))),
+        None => drop(isolate.remove_slot::<DefaultTimeout>()),
+    }
+}
+
+/// Stored in an isolate slot rather than in `OpState`, so that calls without a timeout
+/// only pay for a slot lookup.
+struct DefaultTimeout(Duration);
+
+/// Terminates JavaScript execution if a call does not finish in time.
+///
+/// Used by generated code to implement timeouts.
+#[doc(hidden)]
+pub struct Watchdog(Option<Armed>);
+
+struct Armed {
+    timeout: Duration,
+    isolate: v8::IsolateHandle,
+    deadline: Arc<Deadline>,
+    signal: oneshot::Receiver<()>,
+}
+
+/// A deadline shared between a call and the watchdog thread of its runtime.
+struct Deadline {
+    at: Instant,
+    state: Mutex<DeadlineState>,
+}
+
+enum DeadlineState {
+    Armed(oneshot::Sender<()>),
+    Fired,
+    Disarmed,
+}
+
+/// The watchdog thread of a runtime, stored in an isolate slot.
+///
+/// The thread exits once the runtime, and with it the sender, is dropped.
+struct WatchdogThread(mpsc::Sender<Arc<Deadline>>);
+
+impl Watchdog {
+    /// Send a deadline to the watchdog thread, unless there is no timeout for this call.
+    ///
+    /// The thread is started by the first call on each runtime that has a timeout.
+    pub fn arm(rt: &mut JsRuntime, timeout: Option<Duration>) -> Self {
+        let isolate = rt.v8_isolate();
+
+        let Some(timeout) = timeout.or_else(|| isolate.get_slot::<DefaultTimeout>().map(|t| t.0))
+        else {
+            return Self(None);
+        };
+
+        let handle = isolate.thread_safe_handle();
+
+        if isolate.get_slot::<WatchdogThread>().is_none() {
+            isolate.set_slot(WatchdogThread::spawn(handle.clone()));
+        }
+
+        let (notify, signal) = oneshot::channel();
+
+        let deadline = Arc::new(Deadline {
+            at: Instant::now() + timeout,
+            state: Mutex::new(DeadlineState::Armed(notify)),
+        });
+
+        if let Some(thread) = isolate.get_slot::<WatchdogThread>() {
+            thread.0.send(deadline.clone()).ok();
+        }
+
+        Self(Some(Armed {
+            timeout,
+            isolate: handle,
+            deadline,
+            signal,
+        }))
+    }
+
+    /// Stop the watchdog, and report a timeout if it fired before the call succeeded.
+    ///
+    /// If execution was terminated, the isolate is made usable again.
+    pub fn disarm<T, E: From<Error>>(mut self, result: Result<T, E>) -> Result<T, E> {
+        let Some(armed) = self.0.take() else {
+            return result;
+        };
+
+        if !armed.disarm() {
+            return result;
+        }
+
+        match result {
+            Ok(value) => Ok(value),
+            Err(_) => Err(Error::timeout(armed.timeout).into()),
+        }
+    }
+
+    /// Run `future` until it completes, or until the watchdog fires.
+    ///
+    /// Termination only stops JavaScript that is running, so `future` is also abandoned if
+    /// it is waiting on something else when the timeout elapses.
+    pub async fn guard<T, E, F>(mut self, future: F) -> Result<T, E>
+    where
+        E: From<Error>,
+        F: Future<Output = Result<T, E>>,
+    {
+        let Some(armed) = &mut self.0 else {
+            return future.await;
+        };
+
+        let result = match select(pin!(future), &mut armed.signal).await {
+            Either::Left((result, _)) => result,
+            Either::Right(_) => Err(Error::timeout(armed.timeout).into()),
+        };
+
+        self.disarm(result)
+    }
+}
+
+impl Armed {
+    /// Whether the deadline fired, in which case the isolate is made usable again.
+    fn disarm(&self) -> bool {
+        let fired = self.deadline.disarm();
+        if fired {
+            self.isolate.cancel_terminate_execution();
+        }
+        fired
+    }
+}
+
+impl Drop for Armed {
+    fn drop(&mut self) {
+        self.disarm();
+    }
+}
+
+impl Deadline {
+    fn is_armed(&self) -> bool {
+        matches!(*self.lock(), DeadlineState::Armed(_))
+    }
+
+    /// Terminate execution, unless the call has finished.
+    ///
+    /// This holds the lock while terminating, so that [`disarm`][Self::disarm] never
+    /// misses a termination that is about to happen.
+    fn fire(&self, isolate: &v8::IsolateHandle) {
+        let mut state = self.lock();
+        if !matches!(*state, DeadlineState::Armed(_)) {
+            return;
+        }
+        isolate.terminate_execution();
+        if let DeadlineState::Armed(notify) = mem::replace(&mut *state, DeadlineState::Fired) {
+            notify.send(()).ok();
+        }
+    }
+
+    /// Whether the deadline fired before the call finished.
+    fn disarm(&self) -> bool {
+        let state = mem::replace(&mut *self.lock(), DeadlineState::Disarmed);
+        matches!(state, DeadlineState::Fired)
+    }
+
+    fn lock(&self) -> MutexGuard<'_, DeadlineState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl WatchdogThread {
+    fn spawn(isolate: v8::IsolateHandle) -> Self {
+        let (sender, deadlines) = mpsc::channel();
+        thread::spawn(move || Self::run(&isolate, &deadlines));
+        Self(sender)
+    }
+
+    /// Wait for deadlines from calls, and terminate execution when one of them elapses.
+    fn run(isolate: &v8::IsolateHandle, deadlines: &mpsc::Receiver<Arc<Deadline>>) {
+        let mut pending: Vec<Arc<Deadline>> = vec![];
+        loop {
+            pending.retain(|deadline| deadline.is_armed());
+
+            let received = match pending.iter().map(|deadline| deadline.at).min() {
+                None => deadlines.recv().map_err(|_| RecvTimeoutError::Disconnected),
+                Some(at) => deadlines.recv_timeout(at.saturating_duration_since(Instant::now())),
+            };
+
+            match received {
+                Ok(deadline) => pending.push(deadline),
+                Err(RecvTimeoutError::Timeout) => {
+                    let now = Instant::now();
+                    pending
+                        .iter()
+                        .filter(|deadline| deadline.at <= now)
+                        .for_each(|deadline| deadline.fire(isolate));
+                }
+                Err(RecvTimeoutError::Disconnected) => return,
+            }
+        }
+    }
+}
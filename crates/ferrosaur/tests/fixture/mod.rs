@@ -7,6 +7,7 @@ use tap::Tap;
 pub fn deno() -> Result<JsRuntime> {
     example_runtime::with_options(RuntimeOptions {
         module_loader: Some(Rc::new(items::modules()?)),
+        extensions: vec![items::exports::host::extension()],
         ..Default::default()
     })
 }
@@ -1,15 +1,26 @@
-use std::time::Duration;
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+    time::Duration,
+};
 
 use anyhow::Result;
-use deno_core::v8;
+use deno_core::{futures::StreamExt, v8};
+use ferrosaur::{
+    convert::{ErrorObject, RegExp},
+    CancellationToken, Error, ErrorKind, JsClass, JsPromise, JsThrown,
+};
 use serde_json::json;
 
 mod fixture;
 
 use fixture::{
-    deno,
+    deno, eval_value,
     items::{
+        classes::Counter,
+        errors::ValidationFailure,
         global::Global,
+        hosts::{HostLogger, Logger, LoggerHost},
         modules::{I18n, Main},
         traits::Shape,
     },
@@ -25,6 +36,8 @@ async fn test_functions() -> Result<()> {
     let rectangle = main.rectangle(3.0, 4.0, rt)?;
 
     assert_eq!(rectangle.area(rt)?, 12.0);
+    assert_eq!(rectangle.area_f64(rt)?, 12.0);
+    assert_eq!(rectangle.height_f64(rt)?, 4.0);
 
     assert_eq!(rectangle.value(rt)?, json!("rect 3x4"));
 
@@ -81,6 +94,111 @@ async fn test_promise() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_event_loop_options() -> Result<()> {
+    let rt = &mut deno()?;
+
+    let main = Main::main_module_init(rt).await?;
+
+    assert!(main.sleep_quietly(true, 10, rt).await?);
+
+    assert_eq!(main.increment(41, rt).await?, 42);
+
+    let err = main.sleep_now(true, 10, rt).await.unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::Other(_)));
+    assert!(err.to_string().contains("promise is still pending"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_timeout() -> Result<()> {
+    let rt = &mut deno()?;
+
+    let main = Main::main_module_init(rt).await?;
+
+    let err = main.spin(rt).unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::Timeout { .. }));
+    assert_eq!(err.call_site().map(|site| site.binding), Some("spin"));
+
+    let err = main.spin_later(rt).await.unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::Timeout { .. }));
+
+    let err = main.sleep_briefly(true, 1000, rt).await.unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::Timeout { .. }));
+
+    ferrosaur::set_default_timeout(rt, Some(Duration::from_millis(50)));
+    let err = main.spin_forever(rt).unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::Timeout { .. }));
+    ferrosaur::set_default_timeout(rt, None);
+
+    assert!(main.sleep(true, 0, rt).await?);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_abort_signal() -> Result<()> {
+    let rt = &mut deno()?;
+
+    let main = Main::main_module_init(rt).await?;
+
+    let token = CancellationToken::new();
+    assert_eq!(main.delay(10, token.clone(), rt).await?, "done");
+    assert_eq!(main.aborted_count(rt)?, 0);
+
+    let dropped = tokio::time::timeout(
+        Duration::from_millis(10),
+        main.delay(1000, token.clone(), rt),
+    )
+    .await;
+    assert!(dropped.is_err());
+    rt.run_event_loop(Default::default()).await?;
+    assert_eq!(main.aborted_count(rt)?, 1);
+    assert!(!token.is_cancelled());
+
+    let pending = main.delay_deferred(1000, token.clone(), rt)?;
+    token.cancel();
+    let err = pending.await_in(rt).await.unwrap_err();
+    assert!(err.as_exception().is_some());
+    assert_eq!(main.aborted_count(rt)?, 2);
+
+    let err = main.delay(1000, token, rt).await.unwrap_err();
+    assert!(err.as_exception().is_some());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_deferred_promise() -> Result<()> {
+    let rt = &mut deno()?;
+
+    let main = Main::main_module_init(rt).await?;
+
+    let slow = main.sleep_deferred(false, 50, rt)?;
+    let fast = main.sleep_deferred(true, 10, rt)?;
+    assert_eq!(slow.state(rt), v8::PromiseState::Pending);
+
+    let first = JsPromise::race(rt, [slow, fast]).await?;
+    assert!(first);
+
+    let promises = [
+        main.sleep_deferred(true, 20, rt)?,
+        main.sleep_deferred(false, 10, rt)?,
+    ];
+    let values = JsPromise::all(rt, promises).await?;
+    assert_eq!(values, [true, false]);
+
+    let valid = main.validate_deferred("ok", rt)?;
+    let invalid = main.validate_deferred("", rt)?;
+    assert_eq!(valid.await_in(rt).await?, "OK");
+
+    let err = invalid.await_in(rt).await.unwrap_err();
+    assert!(err.as_exception().is_some());
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_indexing_get() -> Result<()> {
     let rt = &mut deno()?;
@@ -99,6 +217,14 @@ async fn test_indexing_get() -> Result<()> {
 
     assert!(i18n.i18n("", rt).is_err());
 
+    assert_eq!(
+        i18n.i18n_str(
+            "Franz jagt im komplett verwahrlosten Taxi quer durch Bayern",
+            rt,
+        )?,
+        "https://de.wikipedia.org/wiki/Pangramm",
+    );
+
     Ok(())
 }
 
@@ -137,6 +263,23 @@ async fn test_callable() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_callable_overloads() -> Result<()> {
+    let rt = &mut deno()?;
+
+    let main = Main::main_module_init(rt).await?;
+    let money = main.use_money(rt)?;
+
+    assert_eq!(money.call_with_amount(4.5, "EUR", rt)?, "4.50 EUR");
+
+    let created = money.new_money(12.5, "JPY", rt)?;
+    assert_eq!(created, json!({ "amount": 12.5, "currency": "JPY" }));
+
+    assert_eq!(money.call_with_money(created, rt)?, "12.50 JPY");
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_variadic_fn() -> Result<()> {
     let rt = &mut deno()?;
@@ -159,3 +302,414 @@ async fn test_variadic_fn() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_primitives() -> Result<()> {
+    let rt = &mut deno()?;
+
+    let global = Global::new(rt);
+
+    assert_eq!(global.parse_u8("255", rt)?, 255);
+    assert!(global.parse_u8("256", rt).is_err());
+    assert!(global.parse_u8("-1", rt).is_err());
+
+    assert_eq!(
+        global.parse_i64("-9007199254740991", rt)?,
+        -9007199254740991
+    );
+    assert!(global.parse_i64("9007199254740992", rt).is_err());
+    assert!(global.parse_i64("1.5", rt).is_err());
+    assert!(global.parse_i64("NaN", rt).is_err());
+
+    assert_eq!(global.from_u64(9007199254740991, rt)?, "9007199254740991");
+    assert!(global.from_u64(u64::MAX, rt).is_err());
+
+    assert_eq!(global.from_char('🦕', rt)?, '🦕');
+
+    assert_eq!(global.parse_float("1.50", rt)?, "1.5");
+    assert!(global.parse_bool("2", rt)?);
+    assert!(!global.parse_bool("0", rt)?);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_buffers() -> Result<()> {
+    let rt = &mut deno()?;
+
+    let main = Main::main_module_init(rt).await?;
+
+    assert_eq!(main.reverse(b"deno", rt)?, b"oned");
+
+    let bytes = bytes::Bytes::from_static(b"ferrosaur");
+    assert_eq!(main.reverse_bytes(bytes, rt)?, &b"ruasorref"[..]);
+
+    let mut buffer = [0u8; 4];
+    main.fill(&mut buffer, 42, rt)?;
+    assert_eq!(buffer, [42; 4]);
+
+    main.fill_later(&mut buffer, 7, rt).await?;
+    assert_eq!(buffer, [7; 4]);
+
+    assert_eq!(main.sum(&[0.5, 1.5, 2.0], rt)?, 4.0);
+
+    assert_eq!(main.range(4, rt)?, [0.0, 0.5, 1.0, 1.5]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_bigint() -> Result<()> {
+    let rt = &mut deno()?;
+
+    let global = Global::new(rt);
+
+    assert_eq!(global.parse_bigint_i64("-42", rt)?, -42);
+    assert_eq!(
+        global.parse_bigint_i64("9223372036854775807", rt)?,
+        i64::MAX
+    );
+    assert!(global.parse_bigint_i64("9223372036854775808", rt).is_err());
+
+    assert_eq!(
+        global.parse_bigint_u64("18446744073709551615", rt)?,
+        u64::MAX
+    );
+    assert!(global.parse_bigint_u64("-1", rt).is_err());
+
+    assert_eq!(
+        global.from_bigint_i128(i128::MIN, rt)?,
+        "-170141183460469231731687303715884105728"
+    );
+
+    for value in [0, 1, -1, i64::MIN as i128 - 1, i128::MIN, i128::MAX] {
+        assert_eq!(global.bigint_i128(value, rt)?, value);
+    }
+
+    for value in [0, 1, u64::MAX as u128 + 1, u128::MAX] {
+        assert_eq!(global.bigint_u128(value, rt)?, value);
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_builtin_objects() -> Result<()> {
+    let rt = &mut deno()?;
+
+    let main = Main::main_module_init(rt).await?;
+    let global = Global::new(rt);
+
+    let epoch = std::time::UNIX_EPOCH;
+    let later = epoch + Duration::from_millis(1_700_000_000_123);
+    assert_eq!(global.date_from(later, rt)?, later);
+    assert_eq!(main.iso_string(later, rt)?, "2023-11-14T22:13:20.123Z");
+    assert_eq!(
+        global.date_parse("1969-12-31T23:59:59.000Z", rt)?,
+        epoch - Duration::from_secs(1)
+    );
+    assert!(global.date_parse("tomorrow", rt).is_err());
+
+    let regexp = global.regexp("^fe(rr)?o", "iu", rt)?;
+    assert_eq!(regexp, RegExp::new("^fe(rr)?o", "iu"));
+    assert!(main.matches(regexp, "FERROSAUR", rt)?);
+    assert!(global.regexp("(", "", rt).is_err());
+
+    let options = eval_value("({ cause: new TypeError('inner') })", rt)?;
+    let error = global.error("outer", options, rt)?;
+    assert_eq!(error.to_string(), "Error: outer");
+    assert!(error.stack.as_deref().unwrap_or_default().contains("outer"));
+    let cause = error.cause.as_deref().unwrap();
+    assert_eq!(
+        (cause.name.as_str(), cause.message.as_str()),
+        ("TypeError", "inner")
+    );
+
+    let error = anyhow::anyhow!("inner").context("middle").context("outer");
+    assert_eq!(
+        main.describe_error(error.into(), rt)?,
+        ["Error: outer", "Error: middle", "Error: inner"]
+    );
+
+    let error = ErrorObject::new("bad input").with_name("RangeError");
+    assert_eq!(main.describe_error(error, rt)?, ["RangeError: bad input"]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_errors() -> Result<()> {
+    let rt = &mut deno()?;
+
+    let global = Global::new(rt);
+
+    let err = global.parse_bigint_i64("one", rt).unwrap_err();
+    let ErrorKind::Exception(exception) = err.kind() else {
+        panic!("expected an exception, got {err:?}")
+    };
+    assert_eq!(exception.name.as_deref(), Some("SyntaxError"));
+    assert!(err
+        .js_stack()
+        .is_some_and(|stack| stack.contains("SyntaxError")));
+
+    let site = err.call_site().expect("should have a call site");
+    assert!(site.receiver.ends_with("Global"));
+    assert_eq!(site.binding, "parse_bigint_i64");
+    assert_eq!(site.path, Some("BigInt"));
+    assert_eq!(site.location.map(|loc| loc.file()), Some(file!()));
+
+    let err = global.from_u64(u64::MAX, rt).unwrap_err();
+    assert!(matches!(
+        err.kind(),
+        ErrorKind::ArgumentConversion {
+            index: 0,
+            name: "v",
+            ..
+        }
+    ));
+
+    let err = global.parse_u8("256", rt).unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::ReturnConversion { .. }));
+
+    let err = global.undefined_func(rt).unwrap_err();
+    assert!(matches!(
+        err.kind(),
+        ErrorKind::NotAFunction { name: Some(name) } if name == "__undefined__"
+    ));
+    assert!(err
+        .to_string()
+        .contains("Global::undefined_func (property __undefined__), called at"));
+
+    let err = anyhow::Error::from(global.undefined_func(rt).unwrap_err());
+    assert!(matches!(
+        err.downcast_ref::<Error>().map(Error::kind),
+        Some(ErrorKind::NotAFunction { .. })
+    ));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_thrown_errors() -> Result<()> {
+    let rt = &mut deno()?;
+
+    let main = Main::main_module_init(rt).await?;
+
+    assert_eq!(main.validate("ok", rt)?, "OK");
+    assert_eq!(main.validate_later("ok", rt).await?, "OK");
+
+    for err in [
+        main.validate("", rt).unwrap_err(),
+        main.validate_later("", rt).await.unwrap_err(),
+    ] {
+        let Some(ValidationFailure::Invalid { field, message }) = err.thrown() else {
+            panic!("expected ValidationError, got {err:?}")
+        };
+        assert_eq!(
+            (field.as_str(), message.as_str()),
+            ("input", "input is required")
+        );
+    }
+
+    let err = main.validate("missing", rt).unwrap_err();
+    let Some(ValidationFailure::Invalid { message, .. }) = err.thrown() else {
+        panic!("expected a subclass of ValidationError, got {err:?}")
+    };
+    assert_eq!(message, "input is missing");
+    assert_eq!(err.call_site().map(|site| site.binding), Some("validate"));
+
+    for err in [
+        main.validate("too long!", rt).unwrap_err(),
+        main.validate_later("too long!", rt).await.unwrap_err(),
+    ] {
+        let Some(ValidationFailure::RangeError(error)) = err.thrown() else {
+            panic!("expected RangeError, got {err:?}")
+        };
+        assert_eq!(error.to_string(), "RangeError: input is too long");
+    }
+
+    for err in [
+        main.validate("null", rt).unwrap_err(),
+        main.validate_later("null", rt).await.unwrap_err(),
+    ] {
+        assert!(matches!(
+            err,
+            JsThrown::Error(ref err) if matches!(err.kind(), ErrorKind::Exception(_))
+        ));
+    }
+
+    let err = anyhow::Error::from(main.validate("", rt).unwrap_err());
+    assert_eq!(err.to_string(), "invalid input: input is required");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_callbacks() -> Result<()> {
+    let rt = &mut deno()?;
+
+    let main = Main::main_module_init(rt).await?;
+
+    let offset = 10;
+    let mapped = main.map_numbers(vec![1, 2, 3], move |v, i| v * 2 + i + offset, rt)?;
+    assert_eq!(mapped, [12, 15, 18]);
+
+    let seen = Rc::new(RefCell::new(vec![]));
+    main.for_each(
+        vec!["🦀".into(), "🦕".into()],
+        {
+            let seen = seen.clone();
+            move |item| seen.borrow_mut().push(item)
+        },
+        rt,
+    )?;
+    assert_eq!(*seen.borrow(), ["🦀", "🦕"]);
+
+    let err = main.map_numbers_u64(vec![1], |_| u64::MAX, rt).unwrap_err();
+    let exception = err.as_exception().expect("should be an exception");
+    assert!(exception
+        .exception_message
+        .contains("cannot be represented exactly as a JavaScript number"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_promise_args() -> Result<()> {
+    let rt = &mut deno()?;
+
+    let main = Main::main_module_init(rt).await?;
+
+    let value = main
+        .double_later(
+            async {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+                Ok(21)
+            },
+            rt,
+        )
+        .await?;
+    assert_eq!(value, 42);
+
+    let err = main
+        .double_later(async { Err(anyhow::anyhow!("not today")) }, rt)
+        .await
+        .unwrap_err();
+    let exception = err.as_exception().expect("should be an exception");
+    assert!(exception.exception_message.contains("not today"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_sequence_args() -> Result<()> {
+    let rt = &mut deno()?;
+
+    let main = Main::main_module_init(rt).await?;
+
+    let pulled = Rc::new(Cell::new(0));
+    let values = {
+        let pulled = pulled.clone();
+        (1..).inspect(move |_| pulled.set(pulled.get() + 1))
+    };
+    let taken = main.take_while_below(values, 4, rt)?;
+    assert_eq!(taken, [1, 2, 3]);
+    assert_eq!(pulled.get(), 4);
+
+    let lines = deno_core::futures::stream::iter(["a", "b", "c"]).then(|line| async move {
+        tokio::time::sleep(Duration::from_millis(1)).await;
+        line.to_owned()
+    });
+    let joined = main.join_lines(lines, rt).await?;
+    assert_eq!(joined, "a\nb\nc");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_class() -> Result<()> {
+    let rt = &mut deno()?;
+
+    Counter::register(rt)?;
+
+    let count: serde_json::Value = {
+        let value = rt.execute_script(
+            "[eval]",
+            "const counter = new Counter(40);
+            const fork = counter.fork();
+            fork.increment(5);
+            [counter.increment(2), fork.value(), fork instanceof Counter, Counter.withLabel('a')]",
+        )?;
+        let scope = &mut rt.handle_scope();
+        let value = v8::Local::new(scope, value);
+        deno_core::serde_v8::from_v8(scope, value)?
+    };
+    assert_eq!(count, json!([42, 45, true, "Counter(a)"]));
+
+    let err = rt
+        .execute_script("[eval]", "Counter(1)")
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("cannot be invoked without 'new'"));
+
+    let err = rt
+        .execute_script("[eval]", "Counter.prototype.value.call({})")
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("not an instance of Counter"));
+
+    let main = Main::main_module_init(rt).await?;
+
+    let class = Counter::constructor(rt)?;
+    assert_eq!(main.count_to(class, 3, rt)?, 3);
+
+    assert_eq!(main.bump_counter(Counter::new(1), rt)?, 11);
+
+    Ok(())
+}
+
+#[derive(Debug, Default)]
+struct Recorder {
+    prefix: RefCell<String>,
+    lines: RefCell<Vec<String>>,
+}
+
+impl LoggerHost for Rc<Recorder> {
+    fn log(&self, message: String) {
+        let line = format!("{}{message}", self.prefix.borrow());
+        self.lines.borrow_mut().push(line);
+    }
+
+    fn prefix(&self) -> String {
+        self.prefix.borrow().clone()
+    }
+
+    fn set_prefix(&self, prefix: String) {
+        *self.prefix.borrow_mut() = prefix;
+    }
+}
+
+#[tokio::test]
+async fn test_host() -> Result<()> {
+    let rt = &mut deno()?;
+
+    let recorder = Rc::new(Recorder::default());
+
+    let logger: HostLogger = recorder.clone().into_js(rt)?.into();
+
+    logger.set_prefix("[rust] ".into(), rt)?;
+    logger.log("from rust".into(), rt)?;
+    assert_eq!(logger.prefix(rt)?, "[rust] ");
+
+    let main = Main::main_module_init(rt).await?;
+
+    let prefix = main.use_logger(logger, vec!["a".into(), "b".into()], rt)?;
+    assert_eq!(prefix, "[rust] [js] ");
+
+    assert_eq!(
+        *recorder.lines.borrow(),
+        ["[rust] from rust", "[rust] [js] a", "[rust] [js] b"]
+    );
+
+    Ok(())
+}
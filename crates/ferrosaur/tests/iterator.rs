@@ -1,6 +1,8 @@
-use std::collections::HashMap;
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
 
 use anyhow::Result;
+use deno_core::futures::{StreamExt, TryStreamExt};
+use ferrosaur::GeneratorState;
 
 mod fixture;
 
@@ -8,7 +10,7 @@ use fixture::{
     deno,
     items::{
         modules::{I18n, Iter},
-        traits::Entries,
+        traits::{ByteChunks, Entries, Listing},
     },
 };
 
@@ -21,7 +23,7 @@ async fn test_iterator() -> Result<()> {
     let numbers = iter
         .fibonacci(20, rt)?
         .into_iter(rt)
-        .collect::<Result<Vec<_>>>()?;
+        .collect::<ferrosaur::Result<Vec<_>>>()?;
 
     assert_eq!(
         numbers,
@@ -31,6 +33,37 @@ async fn test_iterator() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_iterator_return() -> Result<()> {
+    let rt = &mut deno()?;
+
+    let iter = Iter::main_module_init(rt).await?;
+
+    for ticket in iter.tickets(5, rt)?.into_iter(rt) {
+        if ticket? == 2 {
+            break;
+        }
+    }
+    assert_eq!(iter.closed_count(rt)?, 1);
+
+    let tickets = iter
+        .tickets(2, rt)?
+        .into_iter(rt)
+        .collect::<ferrosaur::Result<Vec<_>>>()?;
+    assert_eq!(tickets, [1, 2]);
+    assert_eq!(iter.closed_count(rt)?, 2);
+
+    let mut tickets = iter.tickets(5, rt)?;
+    assert_eq!(tickets.next(rt)?, Some(1));
+    assert_eq!(tickets.throw(String::from("skip"), rt)?, Some(-1));
+    assert_eq!(tickets.next(rt)?, Some(2));
+    assert_eq!(tickets.return_(rt)?, None);
+    assert_eq!(tickets.next(rt)?, None);
+    assert_eq!(iter.closed_count(rt)?, 3);
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_iterator_trait() -> Result<()> {
     let rt = &mut deno()?;
@@ -42,9 +75,155 @@ async fn test_iterator_trait() -> Result<()> {
         .await?
         .entries(rt)?
         .into_iter(rt)
-        .collect::<Result<HashMap<_, _>>>()?;
+        .collect::<ferrosaur::Result<HashMap<_, _>>>()?;
 
     assert_eq!(messages.len(), 3);
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_iterator_batch() -> Result<()> {
+    let rt = &mut deno()?;
+
+    let iter = Iter::main_module_init(rt).await?;
+
+    let numbers = iter
+        .numbers(10, -1, rt)?
+        .into_iter(rt)
+        .collect::<ferrosaur::Result<Vec<_>>>()?;
+    assert_eq!(numbers, (0..10).collect::<Vec<_>>());
+
+    let closed = iter.closed_count(rt)?;
+    for number in iter.numbers(10, -1, rt)?.into_iter(rt) {
+        if number? == 1 {
+            break;
+        }
+    }
+    assert_eq!(iter.closed_count(rt)?, closed + 1);
+
+    let mut numbers = iter.numbers(10, 6, rt)?.into_iter(rt);
+    for expected in 0..6 {
+        assert_eq!(numbers.next().transpose()?, Some(expected));
+    }
+    assert!(numbers.next().is_some_and(|number| number.is_err()));
+    assert!(numbers.next().is_none());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_shared_iterator() -> Result<()> {
+    let mut rt = deno()?;
+
+    let iter = Iter::main_module_init(&mut rt).await?;
+
+    let rt = Rc::new(RefCell::new(rt));
+
+    let fibonacci = iter.fibonacci(6, &mut rt.borrow_mut())?;
+    for number in fibonacci.into_shared_iter(rt.clone()) {
+        let number = number?;
+        let rt = &mut rt.borrow_mut();
+        let count = iter.range(number as u32, rt)?.into_iter(rt)?.count();
+        assert_eq!(count, number);
+    }
+
+    let letters = iter.letters(&mut rt.borrow_mut())?;
+    let mut letters = Listing::<String>::into_shared_iter(letters, rt.clone())?;
+    assert_eq!(letters.next().transpose()?, Some("a".into()));
+    {
+        let _borrowed = rt.borrow_mut();
+        assert!(letters.next().is_some_and(|letter| letter.is_err()));
+    }
+    assert!(letters.next().is_none());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_iterable() -> Result<()> {
+    let rt = &mut deno()?;
+
+    let iter = Iter::main_module_init(rt).await?;
+    let i18n = I18n::main_module_init(rt).await?;
+
+    let letters = iter
+        .letters(rt)?
+        .into_iter(rt)?
+        .collect::<ferrosaur::Result<Vec<String>>>()?;
+    assert_eq!(letters, ["a", "b", "c"]);
+
+    let messages = i18n.messages(rt).await?;
+    let count = messages.iter(rt)?.count();
+    let messages = messages
+        .into_iter(rt)?
+        .collect::<ferrosaur::Result<HashMap<_, _>>>()?;
+    assert_eq!(messages.len(), count);
+
+    let primes = iter.primes(rt)?;
+    let sum = primes.iter(rt)?.sum::<ferrosaur::Result<u32>>()?;
+    assert_eq!(sum, 17);
+    assert_eq!(primes.iter(rt)?.count(), 4);
+
+    let closed = iter.closed_count(rt)?;
+    let range = iter.range(10, rt)?;
+    for i in range.iter(rt)? {
+        if i? == 3 {
+            break;
+        }
+    }
+    assert_eq!(iter.closed_count(rt)?, closed + 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_generator() -> Result<()> {
+    let rt = &mut deno()?;
+
+    let iter = Iter::main_module_init(rt).await?;
+
+    let mut acc = iter.accumulate(10, rt)?;
+    assert_eq!(acc.resume(0, rt)?, GeneratorState::Yielded(0));
+    assert_eq!(acc.resume(5, rt)?, GeneratorState::Yielded(5));
+    assert_eq!(
+        acc.resume(10, rt)?,
+        GeneratorState::Complete("2 values".into())
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_async_iterator() -> Result<()> {
+    let rt = &mut deno()?;
+
+    let iter = Iter::main_module_init(rt).await?;
+
+    let mut countdown = iter.countdown(3, rt)?;
+    assert_eq!(countdown.next(rt).await?, Some(3));
+
+    let rest = countdown.into_stream(rt).try_collect::<Vec<_>>().await?;
+    assert_eq!(rest, [2, 1]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_async_iterator_trait() -> Result<()> {
+    let rt = &mut deno()?;
+
+    let iter = Iter::main_module_init(rt).await?;
+
+    let chunks = iter
+        .chunks(vec!["foo".into(), "bar".into()], rt)?
+        .into_stream(rt)
+        .map(|chunk| chunk.map(String::from_utf8))
+        .try_collect::<Vec<_>>()
+        .await?
+        .into_iter()
+        .collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(chunks, ["foo", "bar"]);
+
+    Ok(())
+}
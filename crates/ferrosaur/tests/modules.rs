@@ -1,11 +1,18 @@
 use std::path::PathBuf;
 
 use anyhow::Result;
+use deno_core::RuntimeOptions;
 use tap::Pipe;
 
 mod fixture;
 
-use fixture::{deno, items::modules::Cwd};
+use fixture::{
+    deno,
+    items::{
+        exports::host,
+        modules::{Cwd, Host},
+    },
+};
 
 #[tokio::test]
 async fn test_import_url() -> Result<()> {
@@ -32,3 +39,33 @@ async fn test_arbitrary_url() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_host_module() -> Result<()> {
+    let rt = &mut deno()?;
+
+    let host = Host::main_module_init(rt).await?;
+
+    assert_eq!(
+        host.greet_sum(1.0, 2.0, "Rust".into(), rt)?,
+        "Hello, Rust! 3"
+    );
+    assert_eq!(host.tally(vec![1.0, 2.0, 3.5], rt)?, 6.5);
+    assert_eq!(host.read_or_error("greeting.txt".into(), rt)?, "hello");
+    assert_eq!(
+        host.read_or_error("missing.txt".into(), rt)?,
+        "Error: no such file: missing.txt"
+    );
+    assert_eq!(host.exports(rt)?, ["Tally", "add", "greet", "read"]);
+
+    Ok(())
+}
+
+#[test]
+#[should_panic(expected = "host module \"host:fixture\" was added to the runtime more than once")]
+fn test_host_module_twice() {
+    let _ = example_runtime::with_options(RuntimeOptions {
+        extensions: vec![host::extension(), host::extension()],
+        ..Default::default()
+    });
+}
@@ -23,9 +23,15 @@ declare module "ext:deno_console/01_console.js" {
 }
 
 declare module "ext:deno_web/02_timers.js" {
+  export const clearTimeout: typeof globalThis.clearTimeout;
   export const setTimeout: typeof globalThis.setTimeout;
 }
 
+declare module "ext:deno_web/03_abort_signal.js" {
+  export const AbortController: typeof globalThis.AbortController;
+  export const AbortSignal: typeof globalThis.AbortSignal;
+}
+
 declare module "ext:deno_web/05_base64.js" {
   export const atob: typeof globalThis.atob;
   export const btoa: typeof globalThis.btoa;
@@ -25,7 +25,8 @@ import "ext:deno_webidl/00_webidl.js";
 
 import { Console, setNoColorFns } from "ext:deno_console/01_console.js";
 import { URL } from "ext:deno_url/00_url.js";
-import { setTimeout } from "ext:deno_web/02_timers.js";
+import { clearTimeout, setTimeout } from "ext:deno_web/02_timers.js";
+import { AbortController, AbortSignal } from "ext:deno_web/03_abort_signal.js";
 import { atob, btoa } from "ext:deno_web/05_base64.js";
 import { TextDecoder } from "ext:deno_web/08_text_encoding.js";
 
@@ -42,10 +43,13 @@ const publicFinal = {
 };
 
 Object.entries({
+  AbortController,
+  AbortSignal,
   URL,
   Deno,
   atob,
   btoa,
+  clearTimeout,
   setTimeout,
 }).forEach(([key, value]) =>
   Object.defineProperty(globalThis, key, {
@@ -11,14 +11,16 @@ pub fn deno() -> Result<JsRuntime> {
 }
 
 pub fn with_options(options: RuntimeOptions) -> Result<JsRuntime> {
+    let mut extensions = vec![
+        deno_console::deno_console::init(),
+        deno_webidl::deno_webidl::init(),
+        deno_url::deno_url::init(),
+        deno_web::deno_web::init::<Permissions>(Default::default(), None),
+        test_fixture::init(),
+    ];
+    extensions.extend(options.extensions);
     Ok(JsRuntime::try_new(RuntimeOptions {
-        extensions: vec![
-            deno_console::deno_console::init(),
-            deno_webidl::deno_webidl::init(),
-            deno_url::deno_url::init(),
-            deno_web::deno_web::init::<Permissions>(Default::default(), None),
-            test_fixture::init(),
-        ],
+        extensions,
         ..options
     })?)
 }